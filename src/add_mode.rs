@@ -0,0 +1,388 @@
+use crate::task::{Priority, Task, TaskStatus};
+
+use crate::config::Config;
+
+use crate::dates;
+
+use chrono::{Local, NaiveDate};
+
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+/// The file the add mode history is kept in, so that it is available next session
+const HISTORY_FILE_NAME: &str = ".todo/add_history.txt";
+
+/// The commands available in add mode, shown when starting add mode and with :help
+const HELP: &str = "Type a description and press enter to add a task, or x to exit
+    :start <description>   Adds the task as in progress
+    :done <description>    Adds the task as completed
+    :list <name>           Adds the following tasks to another list
+    :undo                  Removes the last task added
+    !high !medium !low     Sets the priority of the task
+    due:<date>             Sets the due date, such as due:fri or due:2023-01-05";
+
+/// Enum for representing a line entered in add mode
+#[derive(Debug, PartialEq, Eq)]
+pub enum AddModeLine {
+    Exit,
+    Help,
+    Undo,
+    SwitchList(String),
+    AddTask {
+        description: String,
+        status: TaskStatus,
+        priority: Option<Priority>,
+        due: Option<NaiveDate>,
+    },
+}
+
+/// Struct for storing the state of an add mode session
+pub struct AddMode {
+    /// The list tasks are being added to
+    list: String,
+    /// The tasks added this session, so that they can be undone
+    added_tasks: Vec<Task>,
+}
+
+impl AddMode {
+    /// Starts an add mode session that adds tasks to the current list
+    ///
+    /// Parameters
+    /// config:   The user's config
+    pub fn new(config: &Config) -> AddMode {
+        AddMode {
+            list: config.current_list(),
+            added_tasks: Vec::new(),
+        }
+    }
+
+    /// Handles a line entered by the user, returning whether add mode should keep running and a
+    /// message to show. Messages for errors are always returned, others only if the config's
+    /// command_feedback is on
+    ///
+    /// Parameters
+    /// line:          The line the user entered
+    /// tasks:         The task vec of the current list
+    /// other_tasks:   The vec containing tasks not currently in the active list
+    /// config:        The user's config
+    pub fn handle_line(
+        &mut self,
+        line: &str,
+        tasks: &mut Vec<Task>,
+        other_tasks: &mut Vec<Task>,
+        config: &Config,
+    ) -> (bool, Option<String>) {
+        let feedback = |message: String| match config.command_feedback() {
+            true => Some(message),
+            false => None,
+        };
+
+        let today = Local::now().date_naive();
+
+        let line = match parse_line(line, today) {
+            Ok(line) => line,
+            Err(err) => return (true, Some(err)),
+        };
+
+        match line {
+            AddModeLine::Exit => (false, feedback(String::from("Exited!"))),
+            AddModeLine::Help => (true, Some(String::from(HELP))),
+            AddModeLine::Undo => {
+                let task = match self.added_tasks.pop() {
+                    Some(task) => task,
+                    None => return (true, Some(String::from("Nothing to undo!"))),
+                };
+
+                let list_tasks = match task.list() == config.current_list() {
+                    true => tasks,
+                    false => other_tasks,
+                };
+
+                if let Some(index) = list_tasks.iter().rposition(|other| other == &task) {
+                    list_tasks.remove(index);
+                }
+
+                (true, feedback(format!("Removed '{}'!", task.description())))
+            }
+            AddModeLine::SwitchList(list) => {
+                if !config.is_valid_list(&list) {
+                    return (true, Some(String::from("That list doesn't exist!")));
+                }
+
+                self.list = list;
+
+                (true, feedback(format!("Adding tasks to {}!", self.list)))
+            }
+            AddModeLine::AddTask {
+                description,
+                status,
+                priority,
+                due,
+            } => {
+                let mut task = match crate::new_task(description, config) {
+                    Ok(task) => task,
+                    Err(err) => return (true, Some(String::from(err))),
+                };
+
+                task.set_list(self.list.clone());
+                task.set_priority(priority);
+                task.set_due(due);
+
+                if status != TaskStatus::NotStarted {
+                    task.update_status(status);
+                }
+
+                self.added_tasks.push(task.clone());
+
+                match task.list() == config.current_list() {
+                    true => tasks.push(task),
+                    false => other_tasks.push(task),
+                }
+
+                (true, feedback(String::from("Added task!")))
+            }
+        }
+    }
+
+    /// Returns the prompt to show, which includes the list tasks are being added to
+    pub fn prompt(&self) -> String {
+        format!("{} > ", self.list)
+    }
+}
+
+/// Parses a line entered in add mode, returning an error message if it is invalid
+///
+/// Parameters
+/// line:    The line the user entered
+/// today:   The day to take relative due dates from
+pub fn parse_line(line: &str, today: NaiveDate) -> Result<AddModeLine, String> {
+    let line = line.trim();
+
+    // Kept for compatibility with the original add mode
+    if line.to_lowercase() == "x" {
+        return Ok(AddModeLine::Exit);
+    }
+
+    let (command, rest) = match line.strip_prefix(':') {
+        Some(command) => command.split_once(' ').unwrap_or((command, "")),
+        None => return parse_task(line, TaskStatus::NotStarted, today),
+    };
+
+    let rest = rest.trim();
+
+    match command {
+        "q" | "exit" => Ok(AddModeLine::Exit),
+        "help" => Ok(AddModeLine::Help),
+        "undo" => Ok(AddModeLine::Undo),
+        "list" if !rest.is_empty() => Ok(AddModeLine::SwitchList(rest.to_owned())),
+        "list" => Err(String::from("Give the name of the list to switch to!")),
+        "start" => parse_task(rest, TaskStatus::InProgress, today),
+        "done" => parse_task(rest, TaskStatus::Completed, today),
+        _ => Err(format!(
+            "Unknown command ':{}', type :help for help",
+            command
+        )),
+    }
+}
+
+/// Parses the description of a task, pulling out any inline priority or due date
+///
+/// Parameters
+/// line:     The description with any inline metadata
+/// status:   The status to add the task with
+/// today:    The day to take relative due dates from
+fn parse_task(line: &str, status: TaskStatus, today: NaiveDate) -> Result<AddModeLine, String> {
+    let mut description: Vec<&str> = Vec::new();
+
+    let mut priority = None;
+
+    let mut due = None;
+
+    for word in line.split_whitespace() {
+        if let Some(date) = word.strip_prefix("due:") {
+            match dates::parse_date(date, today) {
+                Some(date) => due = Some(date),
+                None => return Err(format!("Couldn't understand the due date '{}'", date)),
+            }
+            continue;
+        }
+
+        let word_priority = match word.to_lowercase().as_str() {
+            "!high" | "!h" => Some(Priority::High),
+            "!medium" | "!m" => Some(Priority::Medium),
+            "!low" | "!l" => Some(Priority::Low),
+            _ => None,
+        };
+
+        match word_priority {
+            Some(word_priority) => priority = Some(word_priority),
+            None => description.push(word),
+        }
+    }
+
+    Ok(AddModeLine::AddTask {
+        description: description.join(" "),
+        status,
+        priority,
+        due,
+    })
+}
+
+/// Runs add mode, reading lines with history and line editing until the user exits
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// dry_run:       Whether to leave the history file as it was
+pub fn run(
+    tasks: &mut Vec<Task>,
+    other_tasks: &mut Vec<Task>,
+    config: &Config,
+    dry_run: bool,
+) -> Result<(), ReadlineError> {
+    let mut editor = DefaultEditor::new()?;
+
+    // There won't be any history the first time add mode is used
+    editor.load_history(HISTORY_FILE_NAME).unwrap_or(());
+
+    let mut add_mode = AddMode::new(config);
+
+    crate::print_info(HELP);
+
+    loop {
+        let line = match editor.readline(&add_mode.prompt()) {
+            Ok(line) => line,
+            // Ctrl-C and Ctrl-D exit add mode
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(err) => return Err(err),
+        };
+
+        if !line.trim().is_empty() {
+            editor.add_history_entry(line.as_str())?;
+        }
+
+        let (running, message) = add_mode.handle_line(&line, tasks, other_tasks, config);
+
+        if let Some(message) = message {
+            crate::print_info(&message);
+        }
+
+        if !running {
+            break;
+        }
+    }
+
+    // Failing to save the history shouldn't lose the tasks that were added
+    if !dry_run {
+        editor.save_history(HISTORY_FILE_NAME).unwrap_or(());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::date;
+
+    #[test]
+    /// Tests if parse_line pulls the inline metadata and status prefixes out of the description
+    fn parse_line_handles_metadata() {
+        // A Wednesday
+        let today = date(2023, 1, 4);
+
+        assert_eq!(
+            parse_line(":start Write the docs !high due:fri", today).unwrap(),
+            AddModeLine::AddTask {
+                description: String::from("Write the docs"),
+                status: TaskStatus::InProgress,
+                priority: Some(Priority::High),
+                due: Some(date(2023, 1, 6)),
+            }
+        );
+
+        assert_eq!(
+            parse_line(":done Release", today).unwrap(),
+            AddModeLine::AddTask {
+                description: String::from("Release"),
+                status: TaskStatus::Completed,
+                priority: None,
+                due: None,
+            }
+        );
+    }
+
+    #[test]
+    /// Tests if parse_line understands the add mode commands
+    fn parse_line_handles_commands() {
+        let today = date(2023, 1, 4);
+
+        assert_eq!(parse_line("x", today).unwrap(), AddModeLine::Exit);
+        assert_eq!(parse_line(":q", today).unwrap(), AddModeLine::Exit);
+        assert_eq!(parse_line(":undo", today).unwrap(), AddModeLine::Undo);
+        assert_eq!(
+            parse_line(":list Dev", today).unwrap(),
+            AddModeLine::SwitchList(String::from("Dev"))
+        );
+        assert!(parse_line(":list", today).is_err());
+        assert!(parse_line(":unknown", today).is_err());
+        assert!(parse_line("A task due:someday", today).is_err());
+    }
+
+    #[test]
+    /// Tests if tasks are added to the list switched to, and undo removes the last one
+    fn handle_line_switches_lists_and_undoes() {
+        let mut config = Config::new();
+        config.add_list(String::from("Dev")).unwrap();
+
+        let mut tasks = Vec::new();
+        let mut other_tasks = Vec::new();
+
+        let mut add_mode = AddMode::new(&config);
+
+        add_mode.handle_line("A basic task", &mut tasks, &mut other_tasks, &config);
+        add_mode.handle_line(":list Dev", &mut tasks, &mut other_tasks, &config);
+        add_mode.handle_line(":start A dev task", &mut tasks, &mut other_tasks, &config);
+        add_mode.handle_line("Another dev task", &mut tasks, &mut other_tasks, &config);
+
+        assert_eq!(add_mode.prompt(), "Dev > ");
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(other_tasks.len(), 2);
+        assert_eq!(other_tasks[0].list(), "Dev");
+        assert_eq!(other_tasks[0].status(), TaskStatus::InProgress);
+
+        let (running, message) =
+            add_mode.handle_line(":undo", &mut tasks, &mut other_tasks, &config);
+
+        assert!(running);
+        assert_eq!(message, Some(String::from("Removed 'Another dev task'!")));
+        assert_eq!(other_tasks.len(), 1);
+    }
+
+    #[test]
+    /// Tests if handle_line only gives feedback when command_feedback is on, but always gives
+    /// errors
+    fn handle_line_respects_command_feedback() {
+        let mut config = Config::new();
+        config.set_command_feedback(false);
+
+        let mut tasks = Vec::new();
+        let mut other_tasks = Vec::new();
+
+        let mut add_mode = AddMode::new(&config);
+
+        let (_, message) = add_mode.handle_line("A task", &mut tasks, &mut other_tasks, &config);
+
+        assert_eq!(message, None);
+
+        let (_, message) = add_mode.handle_line(":list Dev", &mut tasks, &mut other_tasks, &config);
+
+        assert_eq!(message, Some(String::from("That list doesn't exist!")));
+
+        let (running, _) = add_mode.handle_line("x", &mut tasks, &mut other_tasks, &config);
+
+        assert!(!running);
+    }
+}
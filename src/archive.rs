@@ -0,0 +1,138 @@
+use crate::task::Task;
+
+use crate::search;
+
+use colored::Colorize;
+
+use std::fmt::Write;
+
+/// Moves every completed or cancelled task out of the tasks vec and onto the end of the archive,
+/// keeping the order they were in. Tasks completed before completion times were recorded are
+/// archived without one
+///
+/// Parameters
+/// tasks:     The vec to take the finished tasks from
+/// archive:   The archived tasks
+pub fn archive_finished(tasks: &mut Vec<Task>, archive: &mut Vec<Task>) {
+    let (finished, unfinished): (Vec<Task>, Vec<Task>) = std::mem::take(tasks)
+        .into_iter()
+        .partition(Task::is_finished);
+
+    *tasks = unfinished;
+
+    archive.extend(finished);
+}
+
+/// Returns whether the task matches the query in its description, tags or notes, in the same way
+/// as the search command
+///
+/// Parameters
+/// task:    The archived task
+/// query:   The text to search for
+fn matches(task: &Task, query: &str) -> bool {
+    let mut fields = vec![task.description(), task.tags().join(" ")];
+
+    fields.extend(task.notes());
+
+    fields
+        .iter()
+        .any(|field| search::fuzzy_match(query, field).is_some())
+}
+
+/// Renders the archived tasks with their archive numbers, which are used to unarchive them, and
+/// the date each was completed if it is known
+///
+/// Parameters
+/// archive:   The archived tasks
+/// list:      The list to show the archived tasks of, or None for every list
+/// query:     Text the tasks must match, if given
+pub fn render_archive(archive: &[Task], list: Option<&str>, query: Option<&str>) -> String {
+    let mut output = String::new();
+
+    for (index, task) in archive.iter().enumerate() {
+        if list.is_some_and(|list| task.list() != list) {
+            continue;
+        }
+
+        if query.is_some_and(|query| !matches(task, query)) {
+            continue;
+        }
+
+        write!(
+            output,
+            "{}. {} {}",
+            index + 1,
+            format!("[{}]", task.status_glyph()).bold(),
+            task.description()
+        )
+        .unwrap();
+
+        // The list is only needed when tasks from every list are shown
+        if list.is_none() {
+            write!(output, " {}", format!("({})", task.list()).bright_black()).unwrap();
+        }
+
+        if let Some(completed) = task.completed() {
+            write!(
+                output,
+                " {}",
+                format!("completed {}", completed.format("%Y-%m-%d")).bright_black()
+            )
+            .unwrap();
+        }
+
+        output.push('\n');
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task;
+
+    use crate::task::TaskStatus;
+
+    #[test]
+    /// Tests if only finished tasks are archived, keeping their order
+    fn archive_finished_works() {
+        let mut tasks = vec![
+            task("Design", TaskStatus::Completed, "Main"),
+            task("Ship", TaskStatus::InProgress, "Main"),
+            task("Scrapped", TaskStatus::Cancelled, "Main"),
+        ];
+
+        let mut archive = vec![task("Older", TaskStatus::Completed, "Dev")];
+
+        archive_finished(&mut tasks, &mut archive);
+
+        assert_eq!(tasks, vec![task("Ship", TaskStatus::InProgress, "Main")]);
+
+        let descriptions: Vec<String> = archive.iter().map(Task::description).collect();
+
+        assert_eq!(descriptions, vec!["Older", "Design", "Scrapped"]);
+    }
+
+    #[test]
+    /// Tests if the archive can be shown for one list and searched, keeping each task's number
+    fn render_archive_filters_by_list_and_query() {
+        let archive = vec![
+            task("Write the docs", TaskStatus::Completed, "Main"),
+            task("Fix the login page", TaskStatus::Completed, "Dev"),
+            task("Review the docs", TaskStatus::Cancelled, "Main"),
+        ];
+
+        let output = render_archive(&archive, Some("Main"), None);
+
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.lines().nth(1).unwrap().starts_with("3. "));
+
+        let output = render_archive(&archive, None, Some("login"));
+
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains("Fix the login page"));
+        assert!(output.contains("Dev"));
+    }
+}
@@ -1,40 +1,98 @@
-use clap::{
-    Args,
-    Parser,
-    Subcommand
-};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::task::{Recurrence, TaskId};
+
+use crate::selector::TaskSelector;
+
+use crate::sorting::SortOrder;
 
 #[derive(Debug, Parser)]
 #[clap(author, version, about)]
 pub struct TodoArgs {
     #[clap(subcommand)]
     pub command: Commands,
+
+    #[arg(short, long, global = true)]
+    /// Don't ask before deleting or removing tasks
+    pub yes: bool,
+
+    #[arg(long, global = true)]
+    /// Show what the command would change in the tasks and config without writing anything
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// Sort and view tasks in the current list
     Tasks(TasksCommand),
-    /// Removes any completed tasks in the current list
+    /// View the tasks in the current list as a board with a column for each status
+    Board(BoardCommand),
+    /// View the agenda for today: tasks in progress, due today or overdue, or planned for today
+    Today(TodayCommand),
+    /// Moves any completed or cancelled tasks in the current list to the archive
     Cleanup(CleanupCommand),
+    /// Views and searches the archived tasks in the current list
+    Archive(ArchiveCommand),
+    /// Restores an archived task to its list
+    Unarchive(UnarchiveCommand),
     /// Add a task to the tasklist. Not providing a description enters add mode
     Add(AddCommand),
     /// Update an existing tasks description
     Update(UpdateCommand),
+    /// Edit a task, or every task in the current list, in your $EDITOR
+    Edit(EditCommand),
     /// Deletes a task from the list
     Delete(DeleteCommand),
-    /// Sets the status of a task to In Progress
+    /// Moves a task to another position in the list
+    Mv(MoveCommand),
+    /// Moves a task to the top of the list
+    Top(MoveToEndCommand),
+    /// Moves a task to the bottom of the list
+    Bottom(MoveToEndCommand),
+    /// Sets the status of a task to In Progress and starts timing it
     Start(StartCommand),
+    /// Stops timing a task, or every task being timed
+    Stop(StopCommand),
+    /// Shows the time spent on tasks
+    Time(TimeCommand),
+    /// Starts a task and focuses on it for a pomodoro, counting down in the terminal
+    Focus(FocusCommand),
     /// Sets the status of a task to Completed
     Finish(FinishCommand),
     /// Sets the status of a task to Not Started
     Restart(RestartCommand),
+    /// Sets the status of a task to Cancelled
+    Cancel(CancelCommand),
+    /// Sets the status of a task to any status, including those in the config's workflow
+    SetStatus(SetStatusCommand),
+    /// Sets the status of a task to Blocked, or blocks it until another task is completed
+    Block(BlockCommand),
+    /// Sets the status of a blocked task back to Not Started, or removes a task blocking it
+    Unblock(BlockCommand),
+    /// Makes a task come back once completed, or stops it coming back
+    Repeat(RepeatCommand),
+    /// Plans tasks for a day, putting them on that day's agenda
+    Plan(PlanCommand),
+    /// Adds to, edits or shows the notes of a task
+    Note(NoteCommand),
+    /// Shows everything about a task
+    Show(ShowCommand),
+    /// Searches the descriptions, tags and notes of the tasks in every list
+    Search(SearchCommand),
+    /// Shows how far along each list is and how quickly tasks are being finished
+    Stats(StatsCommand),
     /// For viewing, creating, and deleting lists
     List(ListCommand),
     /// For configuring todo in this project
     Config(ConfigCommand),
+    /// Exports the tasks in every list to another format
+    Export(ExportCommand),
+    /// Imports tasks from another format
+    Import(ImportCommand),
+    /// Opens a full screen interface for managing tasks in every list
+    Ui,
     /// Deletes the .todo directory, with the config and tasks file
-    Nuke
+    Nuke,
 }
 
 #[derive(Debug, Args)]
@@ -43,8 +101,30 @@ pub struct TasksCommand {
     /// Lists all tasks instead of only the tasks in your current list
     pub all: bool,
     #[arg(short, long)]
-    /// Sorts the current list only, even if the --all flag is used
+    /// Sorts the tasks and saves the order, by status unless --sort-by is given. With --all every
+    /// list is sorted
     pub sort: bool,
+    #[arg(long)]
+    /// Shows the tasks sorted by comma separated keys from status, priority, due, created and
+    /// alpha, each optionally followed by :asc or :desc, such as status,due:desc
+    pub sort_by: Option<SortOrder>,
+    #[arg(short, long, conflicts_with = "all")]
+    /// Lists only the Not Started tasks that aren't blocked by another task
+    pub ready: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct BoardCommand {
+    #[arg(short, long)]
+    /// Shows a board for every list instead of only your current list
+    pub all: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct TodayCommand {
+    #[arg(long)]
+    /// Moves the unfinished tasks planned for today or earlier on to tomorrow, in every list
+    pub reset: bool,
 }
 
 #[derive(Debug, Args)]
@@ -53,49 +133,267 @@ pub struct CleanupCommand {
     pub all: bool,
 }
 
+#[derive(Debug, Args)]
+pub struct ArchiveCommand {
+    /// Text to search the archived tasks for, where the letters of each word only need to appear
+    /// in order
+    pub query: Option<String>,
+
+    #[arg(short, long)]
+    /// Shows the archived tasks in every list instead of only your current list
+    pub all: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct UnarchiveCommand {
+    /// The number of the archived task to restore, as shown by the archive command
+    pub archive_id: usize,
+}
+
 #[derive(Debug, Args)]
 pub struct AddCommand {
     /// The description of the task you're adding to the list
-    pub description: Option<String>
+    pub description: Option<String>,
+
+    #[arg(short, long, requires = "description")]
+    /// The Task ID of the task to add this task as a subtask of
+    pub parent: Option<TaskId>,
+
+    #[arg(short, long, requires = "description")]
+    /// How often the task comes back once completed: daily, weekly, monthly or a number of days
+    /// such as 3d
+    pub repeat: Option<Recurrence>,
 }
 
 #[derive(Debug, Args)]
 pub struct DeleteCommand {
-    /// The Task ID's of the task's to delete
-    pub task_ids: Vec<usize>
+    /// The tasks to delete, as Task IDs, ranges such as 1-5, comma separated lists such as
+    /// 3,7,9, all, completed, last or filters such as 'desc~docs'
+    pub task_ids: Vec<TaskSelector>,
+}
+
+#[derive(Debug, Args)]
+pub struct MoveCommand {
+    /// The Task ID of the task to move
+    pub task_id: TaskId,
+
+    /// The position to move the task to, among the tasks at its level
+    pub position: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct MoveToEndCommand {
+    /// The Task ID of the task to move
+    pub task_id: TaskId,
 }
 
 #[derive(Debug, Args)]
 pub struct StartCommand {
-    /// The Task ID of the task to start
-    pub task_ids: Vec<usize>
+    /// The tasks to start, as Task IDs or any other selector accepted by delete
+    pub task_ids: Vec<TaskSelector>,
+
+    #[arg(short, long)]
+    /// Start the task even if it is blocked by another task
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct StopCommand {
+    /// The tasks to stop timing, as Task IDs or any other selector accepted by delete. Not giving
+    /// any stops every timer
+    pub task_ids: Vec<TaskSelector>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TimeFormat {
+    /// Readable text, with the time spent on each task grouped by list
+    Text,
+    /// Csv with the hours spent on each task, for invoicing
+    Csv,
+}
+
+#[derive(Debug, Args)]
+pub struct TimeCommand {
+    #[arg(short, long)]
+    /// Only shows the time spent on tasks in this list
+    pub list: Option<String>,
+
+    #[arg(short, long, allow_hyphen_values = true)]
+    /// Only counts time spent from this date, such as 2023-01-05, yesterday, monday or -7d
+    pub since: Option<String>,
+
+    #[arg(short, long, value_enum, default_value_t = TimeFormat::Text)]
+    /// The format to show the time spent in
+    pub format: TimeFormat,
+
+    #[arg(short, long)]
+    /// The file to write the report to, if not given the report is printed
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct FocusCommand {
+    /// The Task ID of the task to focus on
+    pub task_id: TaskId,
+
+    #[arg(short, long)]
+    /// The length of the pomodoro in minutes, if not given the config's pomodoro_minutes is used
+    pub minutes: Option<u64>,
+
+    #[arg(short, long)]
+    /// Start the task even if it is blocked by another task
+    pub force: bool,
 }
 
 #[derive(Debug, Args)]
 pub struct FinishCommand {
-    /// The Task ID of the task to finish
-    pub task_ids: Vec<usize>
+    /// The tasks to finish, as Task IDs or any other selector accepted by delete
+    pub task_ids: Vec<TaskSelector>,
+
+    #[arg(short, long)]
+    /// Finish the task even if it is blocked by another task
+    pub force: bool,
 }
 
 #[derive(Debug, Args)]
 pub struct RestartCommand {
-    /// The Task ID of the task to finish
-    pub task_ids: Vec<usize>
+    /// The tasks to restart, as Task IDs or any other selector accepted by delete
+    pub task_ids: Vec<TaskSelector>,
+}
+
+#[derive(Debug, Args)]
+pub struct CancelCommand {
+    /// The tasks to cancel, as Task IDs or any other selector accepted by delete
+    pub task_ids: Vec<TaskSelector>,
+}
+
+#[derive(Debug, Args)]
+pub struct SetStatusCommand {
+    /// The Task ID of the task to update
+    pub task_id: TaskId,
+
+    /// The name of the status, such as review or in progress
+    pub status: String,
+
+    #[arg(short, long)]
+    /// Set the status even if the task is blocked by another task
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SearchCommand {
+    /// The text to search for, where the letters of each word only need to appear in order
+    pub query: String,
+}
+
+#[derive(Debug, Args)]
+pub struct BlockCommand {
+    /// The Task ID of the blocked task
+    pub task_id: TaskId,
+
+    #[arg(long)]
+    /// The Task ID of the task that must be completed first, instead of setting the status
+    pub on: Option<TaskId>,
+}
+
+#[derive(Debug, Args)]
+pub struct RepeatCommand {
+    /// The Task ID of the task to make recurring
+    pub task_id: TaskId,
+
+    /// How often the task comes back: daily, weekly, monthly or a number of days such as 3d. Not
+    /// giving one stops the task coming back
+    pub recurrence: Option<Recurrence>,
+}
+
+#[derive(Debug, Args)]
+pub struct PlanCommand {
+    /// The tasks to plan, as Task IDs or any other selector accepted by delete
+    pub task_ids: Vec<TaskSelector>,
+
+    #[arg(short, long)]
+    /// The day to plan the tasks for, such as tomorrow, fri or 2023-01-05. Defaults to today
+    pub date: Option<String>,
+
+    #[arg(short, long, conflicts_with = "date")]
+    /// Removes the tasks from the day they are planned for
+    pub clear: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct NoteCommand {
+    /// The Task ID of the task
+    pub task_id: TaskId,
+
+    /// The text to add to the task's notes. Not giving any shows the notes instead
+    pub text: Option<String>,
+
+    #[arg(short, long, conflicts_with = "text")]
+    /// Edit the task's notes in your $EDITOR
+    pub edit: bool,
+
+    #[arg(short, long, conflicts_with_all = ["text", "edit"])]
+    /// Remove the task's notes
+    pub clear: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ShowFormat {
+    /// Readable text, leaving out any fields the task doesn't have
+    Text,
+    /// Json with every field, for scripts
+    Json,
+}
+
+#[derive(Debug, Args)]
+pub struct ShowCommand {
+    /// The Task ID of the task to show
+    pub task_id: TaskId,
+
+    #[arg(short, long, value_enum, default_value_t = ShowFormat::Text)]
+    /// The format to show the task in
+    pub format: ShowFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StatsFormat {
+    /// Readable text, with a progress bar for each list
+    Text,
+    /// Json with every statistic, for dashboards
+    Json,
+}
+
+#[derive(Debug, Args)]
+pub struct StatsCommand {
+    #[arg(short, long, value_enum, default_value_t = StatsFormat::Text)]
+    /// The format to show the statistics in
+    pub format: StatsFormat,
 }
 
 #[derive(Debug, Args)]
 pub struct UpdateCommand {
     /// The Task ID of the task to update
-    pub task_id: usize,
+    pub task_id: TaskId,
 
     /// The new desciption of the task
     pub new_description: String,
 
     #[arg(short, long)]
-    /// Whether to append to the task instead of replacing the whole desciption. No space added!
+    /// Whether to append to the task instead of replacing the whole desciption, with a space between
     pub append: bool,
 }
 
+#[derive(Debug, Args)]
+pub struct EditCommand {
+    #[arg(required_unless_present = "list")]
+    /// The Task ID of the task to edit
+    pub task_id: Option<TaskId>,
+
+    #[arg(short, long, conflicts_with = "task_id")]
+    /// Edit every task in the current list at once, one task per line
+    pub list: bool,
+}
+
 #[derive(Debug, Args)]
 pub struct ListCommand {
     #[arg(short, long)]
@@ -108,7 +406,7 @@ pub struct ListCommand {
 
     #[arg(short, long)]
     /// Delete a list
-    pub delete: Option<String>
+    pub delete: Option<String>,
 }
 
 #[derive(Debug, Args)]
@@ -135,5 +433,52 @@ pub struct ConfigCommand {
 
     #[arg(long)]
     /// Only shows list names when there is more than one list
-    pub smart_list_names: Option<bool>
+    pub smart_list_names: Option<bool>,
+
+    #[arg(long)]
+    /// Whether a task is completed once all of its subtasks are
+    pub auto_complete_parents: Option<bool>,
+
+    #[arg(long)]
+    /// The width to wrap task descriptions to, or 0 to use the width of the terminal
+    pub wrap_width: Option<usize>,
+
+    #[arg(long)]
+    /// The keys tasks are shown sorted by, such as status,due:desc, or none for the saved order
+    pub default_sort: Option<SortOrder>,
+
+    #[arg(long)]
+    /// The length of a pomodoro in the focus command, in minutes
+    pub pomodoro_minutes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FileFormat {
+    /// A markdown checklist, with a heading for each list
+    Markdown,
+    /// The json produced by taskwarrior's `task export`, with lists as projects
+    Taskwarrior,
+    /// An icalendar file with a VTODO for each task, for calendar apps
+    Ics,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportCommand {
+    #[arg(short, long, value_enum)]
+    /// The format to export the tasks to
+    pub format: FileFormat,
+
+    #[arg(short, long)]
+    /// The file to write the export to, if not given the export is printed
+    pub output: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportCommand {
+    #[arg(short, long, value_enum)]
+    /// The format of the tasks being imported
+    pub format: FileFormat,
+
+    /// The file to import the tasks from, if not given the tasks are read from stdin
+    pub file: Option<String>,
 }
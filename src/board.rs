@@ -0,0 +1,200 @@
+use crate::task::{Task, TaskStatus};
+
+use crate::config::{Config, StatusDefinition};
+
+use crate::task_management;
+
+use crate::wrap;
+
+use colored::Colorize;
+
+/// The number of spaces between columns
+const COLUMN_GAP: usize = 2;
+
+/// The narrowest a column can be, so that very narrow terminals still show something readable
+const MIN_COLUMN_WIDTH: usize = 12;
+
+/// Renders the tasks as a board with a column for each status, in the order of the config's
+/// workflow. Every status in the workflow gets a column, while statuses outside of it only get one
+/// if a task has them. The default workflow's columns are Not Started, In Progress and Completed
+///
+/// Parameters
+/// tasks:    The tasks to put on the board, which should all be in one list
+/// config:   The user's config
+/// width:    The width of the terminal the board is shown in
+pub fn render_board(tasks: &[Task], config: &Config, width: usize) -> String {
+    let mut columns: Vec<TaskStatus> = match config.statuses().is_empty() {
+        true => vec![
+            TaskStatus::NotStarted,
+            TaskStatus::InProgress,
+            TaskStatus::Completed,
+        ],
+        false => config
+            .statuses()
+            .iter()
+            .map(StatusDefinition::status)
+            .collect(),
+    };
+
+    for task in tasks {
+        if !columns.contains(&task.status()) {
+            columns.push(task.status());
+        }
+    }
+
+    let column_width = (width.saturating_sub(COLUMN_GAP * (columns.len() - 1)) / columns.len())
+        .max(MIN_COLUMN_WIDTH);
+
+    let tree = task_management::task_tree(tasks);
+
+    // The lines of each column, without their heading
+    let cells: Vec<Vec<String>> = columns
+        .iter()
+        .map(|status| {
+            let mut lines: Vec<String> = Vec::new();
+
+            for (task_id, index) in tree.iter() {
+                let task = &tasks[*index];
+
+                if &task.status() != status {
+                    continue;
+                }
+
+                let prefix = format!("{}. ", task_id);
+
+                let mut text = task.description();
+
+                if task_management::is_blocked(tasks, *index) {
+                    text.push_str(" (blocked)");
+                }
+
+                // Continuation lines are indented under the description rather than the Task ID
+                let indent = prefix.chars().count();
+
+                for (number, line) in wrap::wrap(&text, column_width.saturating_sub(indent))
+                    .into_iter()
+                    .enumerate()
+                {
+                    match number {
+                        0 => lines.push(format!("{}{}", prefix, line)),
+                        _ => lines.push(format!("{}{}", " ".repeat(indent), line)),
+                    }
+                }
+            }
+
+            lines
+        })
+        .collect();
+
+    let mut board = String::new();
+
+    // The padding is added before the colors, as the color codes would count towards the width
+    let headings: Vec<String> = columns
+        .iter()
+        .map(|status| {
+            let heading = format!("{:<column_width$}", status.to_string());
+
+            match config.status_definition(status) {
+                Some(definition) => heading.color(definition.color.as_str()).bold().to_string(),
+                None => heading.bold().to_string(),
+            }
+        })
+        .collect();
+
+    board.push_str(&join_row(headings));
+
+    let underlines: Vec<String> = columns
+        .iter()
+        .map(|_| "─".repeat(column_width).bright_black().to_string())
+        .collect();
+
+    board.push_str(&join_row(underlines));
+
+    let rows = cells.iter().map(|lines| lines.len()).max().unwrap_or(0);
+
+    for row in 0..rows {
+        let line: Vec<String> = cells
+            .iter()
+            .map(|lines| {
+                format!(
+                    "{:<column_width$}",
+                    lines.get(row).map(String::as_str).unwrap_or("")
+                )
+            })
+            .collect();
+
+        board.push_str(&join_row(line));
+    }
+
+    board
+}
+
+/// Joins the cells of a row of the board, leaving off any padding at the end of the line
+///
+/// Parameters
+/// cells:   The padded cells of the row
+fn join_row(cells: Vec<String>) -> String {
+    format!("{}\n", cells.join(&" ".repeat(COLUMN_GAP)).trim_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task;
+
+    #[test]
+    /// Tests if the board puts each task under its status with its Task ID, wrapping descriptions
+    /// to the column width
+    fn render_board_works() {
+        let tasks = vec![
+            task("Design", TaskStatus::Completed, "Main"),
+            task("Write the release notes", TaskStatus::InProgress, "Main"),
+            task("Ship", TaskStatus::NotStarted, "Main"),
+        ];
+
+        let board = render_board(&tasks, &Config::new(), 40);
+
+        let lines: Vec<&str> = board.lines().collect();
+
+        assert!(lines[0].contains("Not Started"));
+        assert_eq!(lines[2], "3. Ship       2. Write the  1. Design");
+        assert_eq!(lines[3], "                 release");
+        assert_eq!(lines[4], "                 notes");
+    }
+
+    #[test]
+    /// Tests if the board follows the workflow's columns, and adds columns for statuses outside of
+    /// it that tasks have
+    fn render_board_follows_the_workflow() {
+        let mut config = Config::new();
+
+        config.set_statuses(
+            ["Doing", "Review"]
+                .iter()
+                .map(|name| StatusDefinition {
+                    name: String::from(*name),
+                    glyph: name[..1].to_owned(),
+                    color: String::from("blue"),
+                    done: false,
+                    active: false,
+                })
+                .collect(),
+        );
+
+        let tasks = vec![
+            task("Design", TaskStatus::Custom(String::from("Review")), "Main"),
+            task("Ship", TaskStatus::Blocked, "Main"),
+        ];
+
+        let board = render_board(&tasks, &config, 60);
+
+        let headings = board.lines().next().unwrap();
+
+        let position = |heading: &str| headings.find(heading).unwrap();
+
+        assert!(position("Doing") < position("Review"));
+        assert!(position("Review") < position("Blocked"));
+        assert!(board.contains("1. Design"));
+    }
+}
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+use crate::task::{self, TaskStatus};
+
+use crate::sorting::SortOrder;
+
 use colored::Colorize;
 
 use std::fmt::Write;
@@ -7,6 +11,14 @@ use std::fmt::Write;
 /// Const for representing the default value for num_of_tasks
 const DEFAULT_NUM_OF_TASKS: usize = 4;
 
+/// Const for representing the default value for pomodoro_minutes
+const DEFAULT_POMODORO_MINUTES: u64 = 25;
+
+/// Returns the default value for pomodoro_minutes, for configs written before the option existed
+fn default_pomodoro_minutes() -> u64 {
+    DEFAULT_POMODORO_MINUTES
+}
+
 /// Enum of all possible errors concerning the Config type
 #[derive(Debug, PartialEq, Eq)]
 pub enum ListErrors {
@@ -15,8 +27,42 @@ pub enum ListErrors {
     ListAlreadyExists,
 }
 
+/// The order tasks are sorted in when the config doesn't define a workflow
+const DEFAULT_STATUS_ORDER: [TaskStatus; 5] = [
+    TaskStatus::Completed,
+    TaskStatus::Cancelled,
+    TaskStatus::InProgress,
+    TaskStatus::Blocked,
+    TaskStatus::NotStarted,
+];
+
+/// Struct for storing a status in a custom workflow, such as Review in a Kanban flow. A status
+/// named after a built in one, such as InProgress, restyles the built in status
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusDefinition {
+    pub name: String,
+    /// The character shown between the brackets when listing tasks
+    pub glyph: String,
+    /// The name of the glyph's color, such as bright_blue
+    pub color: String,
+    /// Whether tasks with the status count as done
+    #[serde(default)]
+    pub done: bool,
+    /// Whether tasks with the status are being worked on, so they are timed like tasks in progress
+    #[serde(default)]
+    pub active: bool,
+}
+
+impl StatusDefinition {
+    /// Returns the status tasks are given when set to this definition
+    pub fn status(&self) -> TaskStatus {
+        TaskStatus::from_built_in_name(&self.name)
+            .unwrap_or_else(|| TaskStatus::Custom(self.name.clone()))
+    }
+}
+
 /// Struct for storing a users config options
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Config {
     always_show_list_names: bool,
     always_show_task_ids: bool,
@@ -26,6 +72,21 @@ pub struct Config {
     smart_list_names: bool,
     current_list: String,
     lists: Vec<String>,
+    // Defaults so that configs written before the option existed can still be read
+    #[serde(default)]
+    auto_complete_parents: bool,
+    /// The workflow's statuses in order, which is empty for the default workflow
+    #[serde(default)]
+    statuses: Vec<StatusDefinition>,
+    /// The width descriptions are wrapped to, where 0 uses the width of the terminal
+    #[serde(default)]
+    wrap_width: usize,
+    /// The order tasks are shown in, which doesn't change the order they are saved in
+    #[serde(default)]
+    default_sort: SortOrder,
+    /// The length of a pomodoro in the focus command
+    #[serde(default = "default_pomodoro_minutes")]
+    pomodoro_minutes: u64,
 }
 
 impl Config {
@@ -40,11 +101,16 @@ impl Config {
             smart_list_names: true,
             current_list: String::from("Main"),
             lists: vec![String::from("Main")],
+            auto_complete_parents: false,
+            statuses: Vec::new(),
+            wrap_width: 0,
+            default_sort: SortOrder::default(),
+            pomodoro_minutes: DEFAULT_POMODORO_MINUTES,
         }
     }
 
     // Returns a cloned iterator of the lists vec
-    pub fn lists_iter(&self) -> std::slice::Iter<String> {
+    pub fn lists_iter(&self) -> std::slice::Iter<'_, String> {
         self.lists.iter()
     }
 
@@ -118,6 +184,129 @@ impl Config {
         self.command_feedback = value;
     }
 
+    /// Gets the value of auto_complete_parents
+    pub fn auto_complete_parents(&self) -> bool {
+        self.auto_complete_parents
+    }
+
+    /// Sets the value of auto_complete_parents
+    pub fn set_auto_complete_parents(&mut self, value: bool) {
+        self.auto_complete_parents = value;
+    }
+
+    /// Gets the value of wrap_width
+    pub fn wrap_width(&self) -> usize {
+        self.wrap_width
+    }
+
+    /// Sets the value of wrap_width
+    pub fn set_wrap_width(&mut self, value: usize) {
+        self.wrap_width = value;
+    }
+
+    /// Gets a clone of default_sort
+    pub fn default_sort(&self) -> SortOrder {
+        self.default_sort.clone()
+    }
+
+    /// Sets the value of default_sort
+    pub fn set_default_sort(&mut self, value: SortOrder) {
+        self.default_sort = value;
+    }
+
+    /// Gets the value of pomodoro_minutes
+    pub fn pomodoro_minutes(&self) -> u64 {
+        self.pomodoro_minutes
+    }
+
+    /// Sets the value of pomodoro_minutes
+    pub fn set_pomodoro_minutes(&mut self, value: u64) {
+        self.pomodoro_minutes = value;
+    }
+
+    /// Gets a clone of the statuses of the custom workflow
+    pub fn statuses(&self) -> Vec<StatusDefinition> {
+        self.statuses.clone()
+    }
+
+    /// Sets the statuses of the custom workflow, where an empty vec uses the default workflow
+    pub fn set_statuses(&mut self, statuses: Vec<StatusDefinition>) {
+        self.statuses = statuses;
+    }
+
+    /// Returns the order tasks are sorted in by status. Statuses missing from a custom workflow
+    /// are sorted after it
+    pub fn status_order(&self) -> Vec<TaskStatus> {
+        match self.statuses.is_empty() {
+            true => DEFAULT_STATUS_ORDER.to_vec(),
+            false => self.statuses.iter().map(StatusDefinition::status).collect(),
+        }
+    }
+
+    /// Returns the definition styling the given status, if the workflow has one
+    ///
+    /// Parameters
+    /// status:   The status to find the definition of
+    pub fn status_definition(&self, status: &TaskStatus) -> Option<StatusDefinition> {
+        self.statuses
+            .iter()
+            .find(|definition| &definition.status() == status)
+            .cloned()
+    }
+
+    /// Finds the status with the given name in the workflow, or else a built in status, along
+    /// with whether it counts as done
+    ///
+    /// Parameters
+    /// name:   The name of the status, ignoring case, spaces, dashes and underscores
+    pub fn find_status(&self, name: &str) -> Option<(TaskStatus, bool)> {
+        let normalized = task::normalize_status_name(name);
+
+        let definition = self
+            .statuses
+            .iter()
+            .find(|definition| task::normalize_status_name(&definition.name) == normalized);
+
+        match definition {
+            Some(definition) => {
+                let status = definition.status();
+                let done = self.status_is_done(&status);
+
+                Some((status, done))
+            }
+            None => TaskStatus::from_built_in_name(name)
+                .map(|status| (status.clone(), status == TaskStatus::Completed)),
+        }
+    }
+
+    /// Returns whether tasks given the status count as done. Built in statuses keep their own
+    /// meaning, so only Completed is done, while custom statuses are done if the workflow says so
+    ///
+    /// Parameters
+    /// status:   The status to check
+    pub fn status_is_done(&self, status: &TaskStatus) -> bool {
+        match status {
+            TaskStatus::Custom(_) => self
+                .status_definition(status)
+                .is_some_and(|definition| definition.done),
+            status => status == &TaskStatus::Completed,
+        }
+    }
+
+    /// Returns whether tasks with the given status are being worked on, which In Progress tasks
+    /// always are, and tasks in a custom status are if its definition is marked active
+    ///
+    /// Parameters
+    /// status:   The status to check
+    pub fn status_is_active(&self, status: &TaskStatus) -> bool {
+        match status {
+            TaskStatus::Custom(_) => self
+                .status_definition(status)
+                .is_some_and(|definition| definition.active),
+            status => status == &TaskStatus::InProgress,
+        }
+    }
+
     /// Sets the current list
     ///
     /// Parameters:
@@ -243,6 +432,53 @@ impl Config {
         )
         .unwrap();
 
+        writeln!(
+            options_string,
+            "auto_complete_parents    {}",
+            self.auto_complete_parents()
+        )
+        .unwrap();
+
+        writeln!(
+            options_string,
+            "wrap_width               {}",
+            match self.wrap_width() {
+                0 => String::from("terminal"),
+                width => width.to_string(),
+            }
+        )
+        .unwrap();
+
+        writeln!(
+            options_string,
+            "default_sort             {}",
+            self.default_sort()
+        )
+        .unwrap();
+
+        writeln!(
+            options_string,
+            "pomodoro_minutes         {}",
+            self.pomodoro_minutes()
+        )
+        .unwrap();
+
+        let statuses: Vec<String> = self
+            .statuses
+            .iter()
+            .map(|definition| definition.name.clone())
+            .collect();
+
+        writeln!(
+            options_string,
+            "statuses                 {}",
+            match statuses.is_empty() {
+                true => String::from("default"),
+                false => statuses.join(", "),
+            }
+        )
+        .unwrap();
+
         options_string
     }
 }
@@ -272,7 +508,12 @@ mod tests {
                 num_of_tasks: DEFAULT_NUM_OF_TASKS,
                 smart_list_names: true,
                 current_list: String::from("Main"),
-                lists: vec![String::from("Main")]
+                lists: vec![String::from("Main")],
+                auto_complete_parents: false,
+                statuses: Vec::new(),
+                wrap_width: 0,
+                default_sort: SortOrder::default(),
+                pomodoro_minutes: DEFAULT_POMODORO_MINUTES,
             }
         )
     }
@@ -395,6 +636,7 @@ mod tests {
 
     #[test]
     /// Tests if is_valid_list works as expected
+    #[allow(clippy::bool_assert_comparison)]
     fn is_valid_list_works() {
         let config = Config::new();
 
@@ -501,4 +743,123 @@ mod tests {
 
         assert_eq!(error, ListErrors::ListCannotBeDeleted)
     }
+
+    #[test]
+    /// Tests if set_auto_complete_parents works
+    fn set_auto_complete_parents_works() {
+        let mut config = Config::new();
+
+        config.set_auto_complete_parents(true);
+
+        assert!(config.auto_complete_parents())
+    }
+
+    #[test]
+    /// Tests if set_wrap_width works
+    fn set_wrap_width_works() {
+        let mut config = Config::new();
+
+        config.set_wrap_width(80);
+
+        assert_eq!(config.wrap_width(), 80)
+    }
+
+    #[test]
+    /// Tests if set_default_sort works
+    fn set_default_sort_works() {
+        let mut config = Config::new();
+
+        let order: SortOrder = "priority:desc,due".parse().unwrap();
+
+        config.set_default_sort(order.clone());
+
+        assert_eq!(config.default_sort(), order)
+    }
+
+    #[test]
+    /// Tests if set_pomodoro_minutes works
+    fn set_pomodoro_minutes_works() {
+        let mut config = Config::new();
+
+        config.set_pomodoro_minutes(50);
+
+        assert_eq!(config.pomodoro_minutes(), 50)
+    }
+
+    /// Creates a Kanban workflow for the status tests
+    fn kanban_statuses() -> Vec<StatusDefinition> {
+        ["Backlog", "Ready", "Doing", "Review", "Done"]
+            .iter()
+            .map(|name| StatusDefinition {
+                name: String::from(*name),
+                glyph: name[..1].to_owned(),
+                color: String::from("blue"),
+                done: *name == "Done",
+                active: *name == "Doing",
+            })
+            .collect()
+    }
+
+    #[test]
+    /// Tests if find_status finds workflow statuses, and still finds the built in ones
+    fn find_status_works() {
+        let mut config = Config::new();
+
+        assert_eq!(config.find_status("review"), None);
+
+        config.set_statuses(kanban_statuses());
+
+        assert_eq!(
+            config.find_status("review"),
+            Some((TaskStatus::Custom(String::from("Review")), false))
+        );
+        assert_eq!(
+            config.find_status("DONE"),
+            Some((TaskStatus::Custom(String::from("Done")), true))
+        );
+        assert_eq!(
+            config.find_status("in progress"),
+            Some((TaskStatus::InProgress, false))
+        );
+    }
+
+    #[test]
+    /// Tests if In Progress and workflow statuses marked active count as active
+    fn status_is_active_works() {
+        let mut config = Config::new();
+
+        config.set_statuses(kanban_statuses());
+
+        assert!(config.status_is_active(&TaskStatus::InProgress));
+        assert!(config.status_is_active(&TaskStatus::Custom(String::from("Doing"))));
+        assert!(!config.status_is_active(&TaskStatus::Custom(String::from("Review"))));
+        assert!(!config.status_is_active(&TaskStatus::NotStarted));
+    }
+
+    #[test]
+    /// Tests if status_order follows the workflow, or the default order without one
+    fn status_order_works() {
+        let mut config = Config::new();
+
+        assert_eq!(config.status_order(), DEFAULT_STATUS_ORDER.to_vec());
+
+        config.set_statuses(kanban_statuses());
+
+        assert_eq!(
+            config.status_order()[3],
+            TaskStatus::Custom(String::from("Review"))
+        );
+    }
+
+    #[test]
+    /// Tests if configs written before workflows existed still use the default workflow
+    fn old_configs_use_the_default_workflow() {
+        let yaml = serde_yaml::to_string(&Config::new())
+            .unwrap()
+            .replace("statuses: []\n", "");
+
+        let config: Config = serde_yaml::from_str(&yaml).unwrap();
+
+        assert!(config.statuses().is_empty());
+    }
 }
@@ -0,0 +1,85 @@
+use crate::task::Task;
+
+use crate::task_management;
+
+use std::io::{self, IsTerminal, Write};
+
+/// Asks the user to confirm a destructive command, showing what it will remove or change. The
+/// prompt is skipped when assume_yes is set or stdin isn't a terminal, such as in scripts, and the
+/// command goes ahead
+///
+/// Parameters
+/// question:     The question to ask, such as "Delete these tasks?"
+/// details:      The lines describing what will be removed or changed
+/// assume_yes:   Whether the user already confirmed with --yes
+pub fn confirm(question: &str, details: &[String], assume_yes: bool) -> bool {
+    if assume_yes || !io::stdin().is_terminal() {
+        return true;
+    }
+
+    println!("{}", question);
+
+    for line in details {
+        println!("  {}", line);
+    }
+
+    ask("")
+}
+
+/// Asks the user a yes or no question, defaulting to no. The answer is read from stdin even when
+/// it isn't a terminal, so that piped answers such as `echo y | todo nuke` still work
+///
+/// Parameters
+/// question:   The question to ask, which is shown on the same line as the answer, if any
+pub fn ask(question: &str) -> bool {
+    match question.is_empty() {
+        true => print!("[y/N] "),
+        false => print!("{} [y/N] ", question),
+    }
+
+    // The prompt should be shown even though it doesn't end the line
+    if io::stdout().flush().is_err() {
+        return false;
+    }
+
+    let mut answer = String::new();
+
+    if io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    let answer = answer.trim().to_lowercase();
+
+    answer == "y" || answer == "yes"
+}
+
+/// Describes the tasks at the given indexes with their Task IDs, for confirming a command on them
+///
+/// Parameters
+/// tasks:     The task vec the tasks belong to, which should all be in one list
+/// indexes:   The indexes of the tasks to describe
+pub fn describe_tasks(tasks: &[Task], indexes: &[usize]) -> Vec<String> {
+    task_management::task_tree(tasks)
+        .into_iter()
+        .filter(|(_, index)| indexes.contains(index))
+        .map(|(task_id, index)| format!("{}. {}", task_id, tasks[index].description()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirm_skips_the_prompt_with_assume_yes() {
+        assert!(confirm("Delete these tasks?", &[], true));
+    }
+
+    #[test]
+    fn confirm_skips_the_prompt_without_a_terminal() {
+        // Only checkable when the tests aren't run with a terminal as stdin, such as in CI
+        if !io::stdin().is_terminal() {
+            assert!(confirm("Delete these tasks?", &[], false));
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+/// The format dates are written in when shown to the user
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Parses a date given by the user, relative to the given day. The date can be today, tomorrow,
+/// yesterday, a weekday such as fri or friday (the next one, which is today if it's that day), a
+/// number of days or weeks away such as +3 or +2w, or a date in the form YYYY-MM-DD
+///
+/// Parameters
+/// input:   The date the user gave
+/// today:   The day to take relative dates from
+pub fn parse_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+
+    match input.as_str() {
+        "today" => return Some(today),
+        "tomorrow" => return today.checked_add_days(Days::new(1)),
+        "yesterday" => return today.checked_sub_days(Days::new(1)),
+        _ => (),
+    }
+
+    if let Some(weekday) = parse_weekday(&input) {
+        let days_until =
+            (weekday.num_days_from_monday() + 7 - today.weekday().num_days_from_monday()) % 7;
+
+        return today.checked_add_days(Days::new(days_until as u64));
+    }
+
+    if let Some(offset) = input.strip_prefix('+') {
+        let (number, multiplier) = match offset.strip_suffix('w') {
+            Some(number) => (number, 7),
+            None => (offset.strip_suffix('d').unwrap_or(offset), 1),
+        };
+
+        let number: u64 = number.parse().ok()?;
+
+        return today.checked_add_days(Days::new(number.checked_mul(multiplier)?));
+    }
+
+    NaiveDate::parse_from_str(&input, DATE_FORMAT).ok()
+}
+
+/// Parses a date given by the user that is looking back from the given day, such as the start of
+/// a report. Weekdays such as monday give the last one, which is today if it's that day, and
+/// offsets such as -7 or -2w count back. Anything else is parsed as in parse_date
+///
+/// Parameters
+/// input:   The date the user gave
+/// today:   The day to take relative dates from
+pub fn parse_past_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+
+    if let Some(weekday) = parse_weekday(&input) {
+        let days_since =
+            (today.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+
+        return today.checked_sub_days(Days::new(days_since as u64));
+    }
+
+    if let Some(offset) = input.strip_prefix('-') {
+        let (number, multiplier) = match offset.strip_suffix('w') {
+            Some(number) => (number, 7),
+            None => (offset.strip_suffix('d').unwrap_or(offset), 1),
+        };
+
+        let number: u64 = number.parse().ok()?;
+
+        return today.checked_sub_days(Days::new(number.checked_mul(multiplier)?));
+    }
+
+    parse_date(&input, today)
+}
+
+/// Parses the full or shortened name of a weekday
+///
+/// Parameters
+/// input:   The lowercase name of the weekday
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    let weekdays = [
+        ("mon", "monday", Weekday::Mon),
+        ("tue", "tuesday", Weekday::Tue),
+        ("wed", "wednesday", Weekday::Wed),
+        ("thu", "thursday", Weekday::Thu),
+        ("fri", "friday", Weekday::Fri),
+        ("sat", "saturday", Weekday::Sat),
+        ("sun", "sunday", Weekday::Sun),
+    ];
+
+    weekdays
+        .iter()
+        .find(|(short, long, _)| input == *short || input == *long)
+        .map(|(_, _, weekday)| *weekday)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::date;
+
+    #[test]
+    /// Tests if parse_date understands words relative to today
+    fn parse_date_handles_relative_words() {
+        // A Wednesday
+        let today = date(2023, 1, 4);
+
+        assert_eq!(parse_date("today", today), Some(today));
+        assert_eq!(parse_date("Tomorrow", today), Some(date(2023, 1, 5)));
+        assert_eq!(parse_date("yesterday", today), Some(date(2023, 1, 3)));
+    }
+
+    #[test]
+    /// Tests if parse_date gives the next occurrence of a weekday, or today if it is that day
+    fn parse_date_handles_weekdays() {
+        // A Wednesday
+        let today = date(2023, 1, 4);
+
+        assert_eq!(parse_date("fri", today), Some(date(2023, 1, 6)));
+        assert_eq!(parse_date("monday", today), Some(date(2023, 1, 9)));
+        assert_eq!(parse_date("wed", today), Some(today));
+    }
+
+    #[test]
+    /// Tests if parse_date handles offsets and full dates, and rejects anything else
+    fn parse_date_handles_offsets_and_dates() {
+        let today = date(2023, 1, 4);
+
+        assert_eq!(parse_date("+3", today), Some(date(2023, 1, 7)));
+        assert_eq!(parse_date("+3d", today), Some(date(2023, 1, 7)));
+        assert_eq!(parse_date("+2w", today), Some(date(2023, 1, 18)));
+        assert_eq!(parse_date("2023-02-01", today), Some(date(2023, 2, 1)));
+        assert_eq!(parse_date("someday", today), None);
+        assert_eq!(parse_date("+3000000000000000000w", today), None);
+    }
+
+    #[test]
+    /// Tests if parse_past_date gives the last occurrence of a weekday and counts offsets back
+    fn parse_past_date_looks_back() {
+        // A Wednesday
+        let today = date(2023, 1, 4);
+
+        assert_eq!(parse_past_date("monday", today), Some(date(2023, 1, 2)));
+        assert_eq!(parse_past_date("thu", today), Some(date(2022, 12, 29)));
+        assert_eq!(parse_past_date("wed", today), Some(today));
+        assert_eq!(parse_past_date("-7d", today), Some(date(2022, 12, 28)));
+        assert_eq!(parse_past_date("-1w", today), Some(date(2022, 12, 28)));
+        assert_eq!(parse_past_date("yesterday", today), Some(date(2023, 1, 3)));
+        assert_eq!(parse_past_date("-3000000000000000000w", today), None);
+    }
+}
@@ -0,0 +1,317 @@
+use crate::task::Task;
+
+use crate::config::Config;
+
+use crate::time_tracking::TimeSession;
+
+use colored::Colorize;
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// Struct for storing the state a command works on, before or after it runs
+pub struct Snapshot<'a> {
+    pub tasks: &'a [Task],
+    pub archive: &'a [Task],
+    pub sessions: &'a [TimeSession],
+    pub config: &'a Config,
+}
+
+/// Describes how the tasks, archive, timers and config changed while running a command, for
+/// --dry-run.
+/// Tasks are matched by their unique ids, so a task keeps being the same task when its description
+/// changes or it is moved to or from the archive
+///
+/// Parameters
+/// before:   The state before the command ran
+/// after:    The state as it would be written
+pub fn describe_changes(before: &Snapshot, after: &Snapshot) -> Vec<String> {
+    let mut changes: Vec<String> = Vec::new();
+
+    let contains =
+        |tasks: &[Task], task: &Task| tasks.iter().any(|other| other.uid() == task.uid());
+
+    for task in before.tasks {
+        if contains(after.tasks, task) {
+            continue;
+        }
+
+        match contains(after.archive, task) && !contains(before.archive, task) {
+            true => changes.push(format!(
+                "{} archived {}",
+                ">".blue().bold(),
+                describe_task(task)
+            )),
+            false => changes.push(format!("{} {}", "-".red().bold(), describe_task(task))),
+        }
+    }
+
+    for task in after.tasks {
+        let Some(old) = before.tasks.iter().find(|other| other.uid() == task.uid()) else {
+            match contains(before.archive, task) && !contains(after.archive, task) {
+                true => changes.push(format!(
+                    "{} unarchived {}",
+                    "<".blue().bold(),
+                    describe_task(task)
+                )),
+                false => changes.push(format!("{} {}", "+".green().bold(), describe_task(task))),
+            }
+
+            continue;
+        };
+
+        let fields = changed_fields(old, task);
+
+        if !fields.is_empty() {
+            changes.push(format!(
+                "{} {} ({})",
+                "~".yellow().bold(),
+                describe_task(old),
+                fields.join(", ")
+            ));
+        }
+    }
+
+    for list in after.config.lists_iter() {
+        // Only the order of the tasks that are in the list both before and after matters here, as
+        // the rest have already been described
+        let order = |tasks: &[Task], others: &[Task]| -> Vec<String> {
+            tasks
+                .iter()
+                .filter(|task| &task.list() == list)
+                .filter(|task| {
+                    others
+                        .iter()
+                        .any(|other| other.uid() == task.uid() && &other.list() == list)
+                })
+                .map(Task::uid)
+                .collect()
+        };
+
+        if order(before.tasks, after.tasks) != order(after.tasks, before.tasks) {
+            changes.push(format!(
+                "{} The order of the tasks in {}",
+                "~".yellow().bold(),
+                list
+            ));
+        }
+    }
+
+    for session in after.sessions {
+        let old = before
+            .sessions
+            .iter()
+            .find(|other| other.task_uid == session.task_uid && other.start == session.start);
+
+        match (old, session.end) {
+            (None, None) => changes.push(format!(
+                "{} timer started for {}: {}",
+                "+".green().bold(),
+                session.list,
+                session.description
+            )),
+            (Some(old), Some(_)) if old.end.is_none() => changes.push(format!(
+                "{} timer stopped for {}: {}",
+                "~".yellow().bold(),
+                session.list,
+                session.description
+            )),
+            _ => (),
+        }
+    }
+
+    for field in changed_fields(before.config, after.config) {
+        changes.push(format!("{} config {}", "~".yellow().bold(), field));
+    }
+
+    changes
+}
+
+/// Describes a task by its list and description
+///
+/// Parameters
+/// task:   The task to describe
+fn describe_task(task: &Task) -> String {
+    format!("{}: {}", task.list(), task.description())
+}
+
+/// Returns each field that differs between the two values, such as "status: NotStarted ->
+/// Completed"
+///
+/// Parameters
+/// before:   The value before the command ran
+/// after:    The value after the command ran
+fn changed_fields<T: Serialize>(before: &T, after: &T) -> Vec<String> {
+    let fields = |value: &T| match serde_json::to_value(value) {
+        Ok(Value::Object(fields)) => fields,
+        _ => Map::new(),
+    };
+
+    let before = fields(before);
+    let after = fields(after);
+
+    after
+        .iter()
+        .filter_map(|(name, value)| {
+            let old = before.get(name).unwrap_or(&Value::Null);
+
+            match old == value {
+                true => None,
+                false => Some(format!(
+                    "{}: {} -> {}",
+                    name,
+                    describe_value(old),
+                    describe_value(value)
+                )),
+            }
+        })
+        .collect()
+}
+
+/// Describes a field's value, showing strings without quotes and missing values as none
+///
+/// Parameters
+/// value:   The value to describe
+fn describe_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::from("none"),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task_with_uid;
+
+    use crate::task::TaskStatus;
+
+    /// Creates a snapshot of the given tasks, archive and config, without any timers, for the tests
+    fn snapshot<'a>(tasks: &'a [Task], archive: &'a [Task], config: &'a Config) -> Snapshot<'a> {
+        Snapshot {
+            tasks,
+            archive,
+            sessions: &[],
+            config,
+        }
+    }
+
+    #[test]
+    /// Tests if removed, added and changed tasks are described, along with changes to the config
+    fn describe_changes_works() {
+        let before = vec![
+            task_with_uid("Design", TaskStatus::NotStarted, "Main", "1"),
+            task_with_uid("Ship", TaskStatus::NotStarted, "Main", "2"),
+        ];
+
+        let mut shipped = task_with_uid("Ship", TaskStatus::NotStarted, "Main", "2");
+        shipped.update_status(TaskStatus::Blocked);
+
+        let after = vec![
+            shipped,
+            task_with_uid("Write the docs", TaskStatus::NotStarted, "Main", "3"),
+        ];
+
+        let config_before = Config::new();
+        let mut config_after = Config::new();
+        config_after.set_wrap_width(60);
+
+        let changes = describe_changes(
+            &snapshot(&before, &[], &config_before),
+            &snapshot(&after, &[], &config_after),
+        );
+
+        assert_eq!(changes.len(), 4);
+        assert!(changes[0].ends_with("Main: Design"));
+        assert!(changes[1].ends_with("Main: Ship (status: NotStarted -> Blocked)"));
+        assert!(changes[2].ends_with("Main: Write the docs"));
+        assert!(changes[3].ends_with("config wrap_width: 0 -> 60"));
+
+        let reordered = vec![
+            task_with_uid("Ship", TaskStatus::NotStarted, "Main", "2"),
+            task_with_uid("Design", TaskStatus::NotStarted, "Main", "1"),
+        ];
+
+        let changes = describe_changes(
+            &snapshot(&before, &[], &config_before),
+            &snapshot(&reordered, &[], &config_before),
+        );
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].ends_with("The order of the tasks in Main"));
+    }
+
+    #[test]
+    /// Tests if tasks moved to and from the archive are described as archived and unarchived
+    fn describe_changes_describes_the_archive() {
+        let config = Config::new();
+
+        let tasks = vec![
+            task_with_uid("Design", TaskStatus::NotStarted, "Main", "1"),
+            task_with_uid("Ship", TaskStatus::NotStarted, "Main", "2"),
+        ];
+        let archive = vec![task_with_uid("Design", TaskStatus::NotStarted, "Main", "1")];
+
+        let changes = describe_changes(
+            &snapshot(&tasks, &[], &config),
+            &snapshot(&tasks[1..], &archive, &config),
+        );
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].ends_with("archived Main: Design"));
+
+        let changes = describe_changes(
+            &snapshot(&tasks[1..], &archive, &config),
+            &snapshot(&tasks, &[], &config),
+        );
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].ends_with("unarchived Main: Design"));
+    }
+
+    #[test]
+    /// Tests if timers that would be started or stopped are described
+    fn describe_changes_describes_timers() {
+        let config = Config::new();
+
+        let tasks = vec![task_with_uid("Design", TaskStatus::NotStarted, "Main", "1")];
+
+        let running = TimeSession {
+            task_uid: String::from("1"),
+            list: String::from("Main"),
+            description: String::from("Design"),
+            start: chrono::Utc::now(),
+            end: None,
+        };
+
+        let stopped = TimeSession {
+            end: Some(running.start),
+            ..running.clone()
+        };
+
+        let describe = |before: &[TimeSession], after: &[TimeSession]| {
+            describe_changes(
+                &Snapshot {
+                    sessions: before,
+                    ..snapshot(&tasks, &[], &config)
+                },
+                &Snapshot {
+                    sessions: after,
+                    ..snapshot(&tasks, &[], &config)
+                },
+            )
+        };
+
+        let changes = describe(&[], std::slice::from_ref(&running));
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].ends_with("timer started for Main: Design"));
+
+        let changes = describe(&[running], &[stopped]);
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].ends_with("timer stopped for Main: Design"));
+    }
+}
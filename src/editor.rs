@@ -0,0 +1,615 @@
+use crate::task::{Task, TaskId, TaskStatus};
+
+use crate::config::Config;
+
+use crate::task_management;
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The editor used if neither $VISUAL or $EDITOR are set
+const DEFAULT_EDITOR: &str = "vi";
+
+/// How many names are tried for the temporary file before giving up
+const TEMP_FILE_ATTEMPTS: u32 = 16;
+
+/// The prefix of the comment added to the top of a document when the edit to it was invalid
+const ERROR_PREFIX: &str = "# Error: ";
+
+/// The line in a task document that every line after is part of the task's notes
+const NOTES_HEADER: &str = "Notes:";
+
+/// Enum for storing possible errors when editing in the user's editor
+#[derive(Debug, PartialEq, Eq)]
+pub enum EditorErrors {
+    CouldntCreateFile,
+    EditorFailed,
+    CouldntReadFile,
+}
+
+/// A line of the list document, with the Task ID it was given if it is an existing task
+#[derive(Debug, PartialEq, Eq)]
+pub struct ListLine {
+    pub task_id: Option<TaskId>,
+    pub status: TaskStatus,
+    pub description: String,
+}
+
+/// Opens the document in the user's editor until it is edited into a valid document, which is
+/// then returned parsed. If the document is saved without any changes then the edit is cancelled
+/// and None is returned. If the edit is invalid then the document is re-opened with the error as a
+/// comment at the top
+///
+/// Parameters
+/// document:   The document to edit
+/// parse:      Parses the edited document, returning an error message if it is invalid
+pub fn edit_until_valid<T>(
+    document: String,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<T>, EditorErrors> {
+    edit_with(document, open_in_editor, parse)
+}
+
+/// The logic behind edit_until_valid, with the editor passed in so that it can be tested without
+/// opening a real editor
+///
+/// Parameters
+/// document:   The document to edit
+/// editor:     Edits the document, returning the edited document
+/// parse:      Parses the edited document, returning an error message if it is invalid
+fn edit_with<T>(
+    mut document: String,
+    mut editor: impl FnMut(&str) -> Result<String, EditorErrors>,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<T>, EditorErrors> {
+    loop {
+        let edited = editor(&document)?;
+
+        if edited == document {
+            return Ok(None);
+        }
+
+        match parse(&edited) {
+            Ok(parsed) => return Ok(Some(parsed)),
+            Err(error) => {
+                // Removing the last error, so that they don't pile up at the top of the document
+                let without_error: Vec<&str> = edited
+                    .lines()
+                    .filter(|line| !line.starts_with(ERROR_PREFIX))
+                    .collect();
+
+                document = format!("{}{}\n{}\n", ERROR_PREFIX, error, without_error.join("\n"));
+            }
+        }
+    }
+}
+
+/// Writes the document to a temporary file, opens it in the user's editor and returns what the
+/// file contains once the editor is closed
+///
+/// Parameters
+/// document:   The document to edit
+fn open_in_editor(document: &str) -> Result<String, EditorErrors> {
+    let path = create_temp_file(document)?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| DEFAULT_EDITOR.to_owned());
+
+    // The editor can include arguments, such as `code --wait`
+    let mut editor = editor.split_whitespace();
+
+    let program = editor.next().unwrap_or(DEFAULT_EDITOR);
+
+    let status = Command::new(program).args(editor).arg(&path).status();
+
+    let edited = match status {
+        Ok(status) if status.success() => fs::read_to_string(&path),
+        _ => {
+            fs::remove_file(&path).unwrap_or(());
+            return Err(EditorErrors::EditorFailed);
+        }
+    };
+
+    fs::remove_file(&path).unwrap_or(());
+
+    match edited {
+        Ok(edited) => Ok(edited),
+        Err(_) => Err(EditorErrors::CouldntReadFile),
+    }
+}
+
+/// Creates a new file in the temporary directory containing the document, returning its path.
+/// The file must not already exist, so a file or symlink put in its place by someone else is never
+/// written through, and on unix only the user can read it
+///
+/// Parameters
+/// document:   The document to write to the file
+fn create_temp_file(document: &str) -> Result<PathBuf, EditorErrors> {
+    let mut options = OpenOptions::new();
+
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    // Trying a few names in case one is already taken
+    for attempt in 0..TEMP_FILE_ATTEMPTS {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|time| time.subsec_nanos())
+            .unwrap_or_default();
+
+        let path = env::temp_dir().join(format!(
+            "todo-edit-{}-{}-{}.txt",
+            std::process::id(),
+            nanos,
+            attempt
+        ));
+
+        let mut file = match options.open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(_) => return Err(EditorErrors::CouldntCreateFile),
+        };
+
+        if file.write_all(document.as_bytes()).is_err() {
+            fs::remove_file(&path).unwrap_or(());
+            return Err(EditorErrors::CouldntCreateFile);
+        }
+
+        return Ok(path);
+    }
+
+    Err(EditorErrors::CouldntCreateFile)
+}
+
+/// Creates the document for editing a single task
+///
+/// Parameters
+/// task:   The task to create the document for
+pub fn task_to_document(task: &Task) -> String {
+    format!(
+        "# Lines starting with # are ignored. The status can be NotStarted, InProgress, Completed,\n\
+         # Cancelled, Blocked or a status from your workflow, and everything after the {} line is\n\
+         # kept as the task's notes\n\
+         Description: {}\n\
+         Status: {}\n\
+         List: {}\n\
+         {}\n\
+         {}",
+        NOTES_HEADER,
+        task.description(),
+        task.status().name(),
+        task.list(),
+        NOTES_HEADER,
+        task.notes().unwrap_or_default()
+    )
+}
+
+/// Applies an edited task document to a copy of the given task, returning an error message if the
+/// document is invalid
+///
+/// Parameters
+/// document:   The edited document
+/// task:       The task that was edited
+/// config:     The user's config
+pub fn document_to_task(document: &str, task: &Task, config: &Config) -> Result<Task, String> {
+    let mut edited_task = task.clone();
+
+    let mut lines = document.lines();
+
+    let mut found_description = false;
+
+    for line in lines.by_ref() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+
+        if line.trim() == NOTES_HEADER {
+            break;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field.trim().to_lowercase(), value.trim()),
+            None => return Err(format!("Couldn't understand the line '{}'", line)),
+        };
+
+        match field.as_str() {
+            "description" => {
+                if edited_task.update_description(value.to_owned()).is_err() {
+                    return Err(String::from("Tasks cannot have empty descriptions!"));
+                }
+                found_description = true;
+            }
+            "status" => {
+                let (status, done) = match config.find_status(value) {
+                    Some(status) => status,
+                    None => return Err(format!("'{}' isn't a status", value)),
+                };
+
+                // Only updating the status if it changed, so that the completion time is kept
+                if status != edited_task.status() {
+                    edited_task.update_status_as(status, done);
+                }
+            }
+            "list" => {
+                if !config.is_valid_list(&value.to_owned()) {
+                    return Err(format!("The list '{}' doesn't exist", value));
+                }
+                edited_task.set_list(value.to_owned());
+            }
+            _ => return Err(format!("'{}' isn't a field that can be edited", field)),
+        }
+    }
+
+    if !found_description {
+        return Err(String::from("Tasks cannot have empty descriptions!"));
+    }
+
+    edited_task.set_notes(document_to_notes(&lines.collect::<Vec<&str>>().join("\n")));
+
+    Ok(edited_task)
+}
+
+/// Returns the notes in an edited notes document, or None if the notes were removed
+///
+/// Parameters
+/// document:   The edited document
+pub fn document_to_notes(document: &str) -> Option<String> {
+    let notes = document.trim();
+
+    match notes.is_empty() {
+        true => None,
+        false => Some(notes.to_owned()),
+    }
+}
+
+/// Creates the document for editing every task in a list, with one task on each line. The tasks
+/// are numbered and indented as the tasks command shows them, with subtasks following their parent
+///
+/// Parameters
+/// tasks:   The tasks in the list
+/// list:    The name of the list
+pub fn list_to_document(tasks: &[Task], list: &str) -> String {
+    let mut document = format!(
+        "# Editing the tasks in {}. Lines starting with # are ignored\n\
+         # Reorder, reword or delete lines to change the tasks, and lines without a Task ID are\n\
+         # added as new tasks. The status can be [ ] not started, [~] in progress, [x] completed,\n\
+         # [-] cancelled, [!] blocked or the name of a status from your workflow such as [Review]\n",
+        list
+    );
+
+    for (task_id, index) in task_management::task_tree(tasks) {
+        let task = &tasks[index];
+
+        let status = match task.status() {
+            TaskStatus::NotStarted => String::from(" "),
+            TaskStatus::InProgress => String::from("~"),
+            TaskStatus::Completed => String::from("x"),
+            TaskStatus::Cancelled => String::from("-"),
+            TaskStatus::Blocked => String::from("!"),
+            TaskStatus::Custom(name) => name,
+        };
+
+        document.push_str(&format!(
+            "{}{}. [{}] {}\n",
+            "    ".repeat(task_id.depth()),
+            task_id,
+            status,
+            task.description()
+        ));
+    }
+
+    document
+}
+
+/// Parses an edited list document, returning an error message if it is invalid
+///
+/// Parameters
+/// document:   The edited document
+/// task_ids:   The Task IDs of the tasks in the list when it was opened
+/// config:     The user's config, for the statuses in the workflow
+pub fn document_to_list(
+    document: &str,
+    task_ids: &[TaskId],
+    config: &Config,
+) -> Result<Vec<ListLine>, String> {
+    let mut list_lines: Vec<ListLine> = Vec::new();
+
+    for line in document.lines() {
+        let line = line.trim();
+
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+
+        // Reading the Task ID if the line has one
+        let (task_id, rest) = match line.split_once(". ") {
+            Some((task_id, rest)) if task_id.chars().all(|c| c.is_ascii_digit() || c == '.') => {
+                let task_id: TaskId = task_id.parse()?;
+
+                if !task_ids.contains(&task_id) {
+                    return Err(format!("There is no task with the Task ID {}", task_id));
+                }
+
+                if list_lines
+                    .iter()
+                    .any(|line| line.task_id.as_ref() == Some(&task_id))
+                {
+                    return Err(format!("The Task ID {} is used more than once", task_id));
+                }
+
+                (Some(task_id), rest.trim_start())
+            }
+            _ => (None, line),
+        };
+
+        let (status, description) = if let Some(description) = rest.strip_prefix("[ ]") {
+            (TaskStatus::NotStarted, description)
+        } else if let Some(description) = rest.strip_prefix("[~]") {
+            (TaskStatus::InProgress, description)
+        } else if let Some(description) = rest
+            .strip_prefix("[x]")
+            .or_else(|| rest.strip_prefix("[X]"))
+        {
+            (TaskStatus::Completed, description)
+        } else if let Some(description) = rest.strip_prefix("[-]") {
+            (TaskStatus::Cancelled, description)
+        } else if let Some(description) = rest.strip_prefix("[!]") {
+            (TaskStatus::Blocked, description)
+        } else {
+            // Statuses from the workflow are written out by name
+            let workflow_status = rest
+                .strip_prefix('[')
+                .and_then(|rest| rest.split_once(']'))
+                .and_then(|(name, description)| {
+                    config
+                        .find_status(name)
+                        .map(|(status, _)| (status, description))
+                });
+
+            match workflow_status {
+                Some(workflow_status) => workflow_status,
+                None => return Err(format!("The line '{}' doesn't have a valid status", line)),
+            }
+        };
+
+        let description = description.trim();
+
+        if description.is_empty() {
+            return Err(String::from("Tasks cannot have empty descriptions!"));
+        }
+
+        list_lines.push(ListLine {
+            task_id,
+            status,
+            description: description.to_owned(),
+        });
+    }
+
+    Ok(list_lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task;
+
+    use crate::config::StatusDefinition;
+
+    /// Returns the Task IDs of a list of the given number of top level tasks
+    fn task_ids(count: usize) -> Vec<TaskId> {
+        (1..=count).map(TaskId::from).collect()
+    }
+
+    #[test]
+    /// Tests if each edit gets its own new temporary file containing the document
+    fn create_temp_file_creates_new_files() {
+        let first = create_temp_file("A document").unwrap();
+        let second = create_temp_file("Another document").unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "A document");
+
+        fs::remove_file(first).unwrap();
+        fs::remove_file(second).unwrap();
+    }
+
+    #[test]
+    /// Tests if a task document can be edited and applied back to the task
+    fn document_to_task_works() {
+        let mut config = Config::new();
+
+        config.add_list(String::from("Dev")).unwrap();
+
+        let task = task("A basic task", TaskStatus::NotStarted, "Main");
+
+        let document = task_to_document(&task)
+            .replace("A basic task", "A reworded task")
+            .replace("NotStarted", "in progress")
+            .replace("List: Main", "List: Dev")
+            + "Some notes\nover two lines\n";
+
+        let edited_task = document_to_task(&document, &task, &config).unwrap();
+
+        assert_eq!(edited_task.description(), "A reworded task");
+        assert_eq!(edited_task.status(), TaskStatus::InProgress);
+        assert_eq!(edited_task.list(), "Dev");
+        assert_eq!(
+            edited_task.notes(),
+            Some(String::from("Some notes\nover two lines"))
+        );
+    }
+
+    #[test]
+    /// Tests if document_to_task returns an error message for invalid edits
+    fn document_to_task_fails_on_invalid_edits() {
+        let config = Config::new();
+
+        let task = task("A basic task", TaskStatus::NotStarted, "Main");
+
+        let document = task_to_document(&task);
+
+        assert!(
+            document_to_task(&document.replace("NotStarted", "Sleeping"), &task, &config).is_err()
+        );
+        assert!(
+            document_to_task(&document.replace("List: Main", "List: Dev"), &task, &config).is_err()
+        );
+        assert!(document_to_task(&document.replace("A basic task", ""), &task, &config).is_err());
+    }
+
+    #[test]
+    /// Tests if a list document can be reordered, reworded and have lines deleted and added
+    fn document_to_list_works() {
+        let tasks = vec![
+            task("A basic task", TaskStatus::NotStarted, "Main"),
+            task("Another basic task", TaskStatus::InProgress, "Main"),
+            task("Yet another basic task", TaskStatus::Completed, "Main"),
+        ];
+
+        let document = list_to_document(&tasks, "Main");
+
+        assert!(document.ends_with(
+            "1. [ ] A basic task\n2. [~] Another basic task\n3. [x] Yet another basic task\n"
+        ));
+
+        let document = "# A comment\n\
+                        3. [x] Yet another basic task\n\
+                        1. [~] A reworded task\n\
+                        [ ] A new task\n";
+
+        assert_eq!(
+            document_to_list(document, &task_ids(3), &Config::new()).unwrap(),
+            vec![
+                ListLine {
+                    task_id: Some(TaskId::from(3)),
+                    status: TaskStatus::Completed,
+                    description: String::from("Yet another basic task"),
+                },
+                ListLine {
+                    task_id: Some(TaskId::from(1)),
+                    status: TaskStatus::InProgress,
+                    description: String::from("A reworded task"),
+                },
+                ListLine {
+                    task_id: None,
+                    status: TaskStatus::NotStarted,
+                    description: String::from("A new task"),
+                },
+            ]
+        )
+    }
+
+    #[test]
+    /// Tests if subtasks are numbered and indented under their parent, and read back by Task ID
+    fn list_documents_number_subtasks() {
+        let mut tasks = vec![
+            task("A parent task", TaskStatus::NotStarted, "Main"),
+            task("Another basic task", TaskStatus::NotStarted, "Main"),
+            task("A subtask", TaskStatus::NotStarted, "Main"),
+        ];
+
+        tasks[0].set_uid(String::from("1"));
+        tasks[2].set_parent(Some(String::from("1")));
+
+        let document = list_to_document(&tasks, "Main");
+
+        assert!(document.ends_with(
+            "1. [ ] A parent task\n    1.1. [ ] A subtask\n2. [ ] Another basic task\n"
+        ));
+
+        let task_ids: Vec<TaskId> = task_management::task_tree(&tasks)
+            .into_iter()
+            .map(|(task_id, _)| task_id)
+            .collect();
+
+        let list_lines = document_to_list(&document, &task_ids, &Config::new()).unwrap();
+
+        assert_eq!(list_lines[1].task_id, Some("1.1".parse().unwrap()));
+        assert!(document_to_list("1.2. [ ] A task", &task_ids, &Config::new()).is_err());
+    }
+
+    #[test]
+    /// Tests if document_to_list returns an error message for invalid edits
+    fn document_to_list_fails_on_invalid_edits() {
+        assert!(document_to_list("4. [ ] A task", &task_ids(3), &Config::new()).is_err());
+        assert!(
+            document_to_list("1. [ ] A task\n1. [x] A task", &task_ids(3), &Config::new()).is_err()
+        );
+        assert!(document_to_list("1. [?] A task", &task_ids(3), &Config::new()).is_err());
+        assert!(document_to_list("1. [ ] ", &task_ids(3), &Config::new()).is_err());
+    }
+
+    #[test]
+    /// Tests if edit_with re-opens the document with the error after an invalid edit
+    fn edit_with_reopens_invalid_edits() {
+        let mut edits = vec!["invalid", "valid"].into_iter();
+
+        let mut opened_documents: Vec<String> = Vec::new();
+
+        let parsed = edit_with(
+            String::from("original"),
+            |document| {
+                opened_documents.push(document.to_owned());
+                Ok(edits.next().unwrap().to_owned())
+            },
+            |document| match document {
+                "valid" => Ok(document.to_owned()),
+                _ => Err(String::from("Not valid")),
+            },
+        )
+        .unwrap();
+
+        assert_eq!(parsed, Some(String::from("valid")));
+        assert_eq!(opened_documents[1], "# Error: Not valid\ninvalid\n");
+    }
+
+    #[test]
+    /// Tests if edit_with cancels the edit if the document is saved without changes
+    fn edit_with_cancels_unchanged_documents() {
+        let parsed = edit_with(
+            String::from("original"),
+            |document| Ok(document.to_owned()),
+            |document| Ok(document.to_owned()),
+        )
+        .unwrap();
+
+        assert_eq!(parsed, None);
+    }
+
+    #[test]
+    /// Tests if statuses from the workflow are written out by name in list documents and read back
+    fn document_to_list_reads_workflow_statuses() {
+        let mut config = Config::new();
+
+        config.set_statuses(vec![StatusDefinition {
+            name: String::from("Review"),
+            glyph: String::from("R"),
+            color: String::from("blue"),
+            done: false,
+            active: false,
+        }]);
+
+        let tasks = vec![task(
+            "A basic task",
+            TaskStatus::Custom(String::from("Review")),
+            "Main",
+        )];
+
+        let document = list_to_document(&tasks, "Main");
+
+        assert!(document.contains("1. [Review] A basic task"));
+        assert_eq!(
+            document_to_list(&document, &task_ids(1), &config).unwrap()[0].status,
+            TaskStatus::Custom(String::from("Review"))
+        );
+        assert!(document_to_list(&document, &task_ids(1), &Config::new()).is_err());
+    }
+}
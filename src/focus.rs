@@ -0,0 +1,102 @@
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::terminal;
+
+use colored::Colorize;
+
+use std::io::{self, IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+/// How often the countdown is redrawn
+const TICK: Duration = Duration::from_secs(1);
+
+/// Formats the time left in a pomodoro as minutes and seconds, such as 24:05
+///
+/// Parameters
+/// remaining:   The time left
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Returns whether the countdown can be shown, which needs stdin and stdout to be a terminal
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+/// Counts down a pomodoro in the terminal, returning whether it ran to the end, or None if the
+/// terminal couldn't be used for it. Pressing q, Esc or Ctrl-C stops it early
+///
+/// Parameters
+/// description:   The description of the task being focused on
+/// length:        How long the pomodoro lasts
+pub fn countdown(description: &str, length: Duration) -> Option<bool> {
+    let end = Instant::now().checked_add(length)?;
+
+    // Raw mode lets single key presses stop the countdown without waiting for enter
+    if !is_interactive() || terminal::enable_raw_mode().is_err() {
+        return None;
+    }
+
+    let completed = loop {
+        let remaining = end.saturating_duration_since(Instant::now());
+
+        print!(
+            "\r{} {} {}  {}",
+            "Focusing on".bold(),
+            description,
+            format_remaining(remaining).bright_green(),
+            "(q to stop)".bright_black()
+        );
+
+        // Failing to draw the countdown doesn't stop it
+        let _ = io::stdout().flush();
+
+        if remaining.is_zero() {
+            break true;
+        }
+
+        if stopped(remaining.min(TICK)) {
+            break false;
+        }
+    };
+
+    let _ = terminal::disable_raw_mode();
+
+    println!();
+
+    Some(completed)
+}
+
+/// Waits for up to the timeout for a key press, returning whether it was one that stops the
+/// countdown
+///
+/// Parameters
+/// timeout:   How long to wait
+fn stopped(timeout: Duration) -> bool {
+    if !event::poll(timeout).unwrap_or(false) {
+        return false;
+    }
+
+    match event::read() {
+        Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => true,
+            KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Tests if the time left is shown as minutes and seconds
+    fn format_remaining_works() {
+        assert_eq!(format_remaining(Duration::from_secs(25 * 60)), "25:00");
+        assert_eq!(format_remaining(Duration::from_secs(65)), "01:05");
+        assert_eq!(format_remaining(Duration::ZERO), "00:00");
+    }
+}
@@ -0,0 +1,28 @@
+pub mod icalendar;
+pub mod markdown;
+pub mod taskwarrior;
+
+use crate::task::{Task, TaskErrors};
+
+use crate::config::Config;
+
+/// Enum for storing possible errors when importing tasks
+#[derive(Debug, PartialEq, Eq)]
+pub enum ImportErrors {
+    NoTasksFound,
+    InvalidFormat,
+    TaskErrors(TaskErrors),
+}
+
+/// Adds the lists of the imported tasks to the config if they don't exist yet. This is only done
+/// once every task has been read, so that an import that fails part way leaves the config as it was
+///
+/// Parameters
+/// tasks:    The imported tasks
+/// config:   The user's config
+fn add_lists(tasks: &[Task], config: &mut Config) {
+    for task in tasks {
+        // The list already existing is fine, the task is just added to it
+        config.add_list(task.list()).unwrap_or(());
+    }
+}
@@ -0,0 +1,532 @@
+use crate::task::{Priority, Task, TaskStatus};
+
+use crate::config::Config;
+
+use crate::formats::ImportErrors;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use std::collections::BTreeMap;
+
+use std::fmt::Write;
+
+/// The format icalendar uses for times in UTC
+const DATE_TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// The format icalendar uses for dates
+const DATE_FORMAT: &str = "%Y%m%d";
+
+/// The maximum length of a line in an icalendar file, after which it has to be folded
+const MAX_LINE_LENGTH: usize = 75;
+
+/// Exports every task as a VTODO in an icalendar file. The task's list becomes its first category,
+/// followed by its tags
+///
+/// Parameters
+/// tasks:          The vec of tasks in the active list
+/// other_tasks:    The vec containing tasks not currently in the active list
+pub fn export_tasks(tasks: &[Task], other_tasks: &[Task]) -> String {
+    let now = Utc::now().format(DATE_TIME_FORMAT).to_string();
+
+    let mut calendar = String::new();
+
+    write_line(&mut calendar, "BEGIN:VCALENDAR");
+    write_line(&mut calendar, "VERSION:2.0");
+    write_line(&mut calendar, "PRODID:-//todo//todo//EN");
+
+    for task in tasks.iter().chain(other_tasks.iter()) {
+        write_line(&mut calendar, "BEGIN:VTODO");
+        write_line(&mut calendar, &format!("UID:{}", escape_text(&task.uid())));
+        write_line(&mut calendar, &format!("DTSTAMP:{}", now));
+
+        if let Some(created) = task.created() {
+            write_line(
+                &mut calendar,
+                &format!("CREATED:{}", created.format(DATE_TIME_FORMAT)),
+            );
+        }
+
+        write_line(
+            &mut calendar,
+            &format!("SUMMARY:{}", escape_text(&task.description())),
+        );
+
+        let status = match task.status() {
+            // Icalendar has no blocked status, so blocked tasks are exported as needing action
+            TaskStatus::NotStarted | TaskStatus::Blocked => "NEEDS-ACTION",
+            TaskStatus::InProgress => "IN-PROCESS",
+            TaskStatus::Completed => "COMPLETED",
+            TaskStatus::Cancelled => "CANCELLED",
+            // Custom statuses are exported by whether they count as done
+            TaskStatus::Custom(_) if task.is_finished() => "COMPLETED",
+            TaskStatus::Custom(_) => "IN-PROCESS",
+        };
+
+        write_line(&mut calendar, &format!("STATUS:{}", status));
+
+        if let Some(completed) = task.completed() {
+            write_line(
+                &mut calendar,
+                &format!("COMPLETED:{}", completed.format(DATE_TIME_FORMAT)),
+            );
+        }
+
+        let categories: Vec<String> = std::iter::once(task.list())
+            .chain(task.tags())
+            .map(|category| escape_text(&category))
+            .collect();
+
+        write_line(
+            &mut calendar,
+            &format!("CATEGORIES:{}", categories.join(",")),
+        );
+
+        if let Some(due) = task.due() {
+            write_line(
+                &mut calendar,
+                &format!("DUE;VALUE=DATE:{}", due.format(DATE_FORMAT)),
+            );
+        }
+
+        // Icalendar priorities go from 1 being the highest to 9 being the lowest
+        if let Some(priority) = task.priority() {
+            let priority = match priority {
+                Priority::High => 1,
+                Priority::Medium => 5,
+                Priority::Low => 9,
+            };
+
+            write_line(&mut calendar, &format!("PRIORITY:{}", priority));
+        }
+
+        write_line(&mut calendar, "END:VTODO");
+    }
+
+    write_line(&mut calendar, "END:VCALENDAR");
+
+    calendar
+}
+
+/// Reads the VTODOs out of an icalendar file. The first category of each VTODO is used as its
+/// list, which is added to the config if it doesn't exist, and the rest become tags. Along with the
+/// tasks a list of notes is returned, describing anything that couldn't be imported
+///
+/// Parameters
+/// calendar:   The contents of the icalendar file
+/// config:     The user's config
+pub fn import_tasks(
+    calendar: &str,
+    config: &mut Config,
+) -> Result<(Vec<Task>, Vec<String>), ImportErrors> {
+    let lines = unfold_lines(calendar);
+
+    if lines.first().map(|line| line.trim()) != Some("BEGIN:VCALENDAR") {
+        return Err(ImportErrors::InvalidFormat);
+    }
+
+    let mut tasks: Vec<Task> = Vec::new();
+
+    let mut skipped_tasks: BTreeMap<String, usize> = BTreeMap::new();
+
+    // The properties of the VTODO currently being read, if inside one
+    let mut properties: Option<BTreeMap<String, (String, String)>> = None;
+
+    for line in lines {
+        let line = line.trim_end();
+
+        if line == "BEGIN:VTODO" {
+            properties = Some(BTreeMap::new());
+            continue;
+        }
+
+        if line == "END:VTODO" {
+            let vtodo = match properties.take() {
+                Some(vtodo) => vtodo,
+                None => return Err(ImportErrors::InvalidFormat),
+            };
+
+            match vtodo_to_task(&vtodo, config)? {
+                Ok(task) => tasks.push(task),
+                Err(status) => *skipped_tasks.entry(status).or_default() += 1,
+            }
+
+            continue;
+        }
+
+        if let Some(vtodo) = properties.as_mut() {
+            // Lines are in the form NAME;PARAMETERS:VALUE
+            let (name_and_parameters, value) = match line.split_once(':') {
+                Some(property) => property,
+                None => return Err(ImportErrors::InvalidFormat),
+            };
+
+            let (name, parameters) = name_and_parameters
+                .split_once(';')
+                .unwrap_or((name_and_parameters, ""));
+
+            vtodo.insert(
+                name.to_uppercase(),
+                (parameters.to_uppercase(), value.to_owned()),
+            );
+        }
+    }
+
+    let notes = skipped_tasks
+        .into_iter()
+        .map(|(status, count)| {
+            let plural = if count == 1 { "task" } else { "tasks" };
+            format!("Skipped {} {} {}", count, status.to_lowercase(), plural)
+        })
+        .collect();
+
+    if tasks.is_empty() {
+        return Err(ImportErrors::NoTasksFound);
+    }
+
+    super::add_lists(&tasks, config);
+
+    Ok((tasks, notes))
+}
+
+/// Converts the properties of a VTODO into a task. If the VTODO has a status with no equivalent
+/// then the status is returned instead, so that it can be reported
+///
+/// Parameters
+/// vtodo:    The properties of the VTODO, mapped to their parameters and value
+/// config:   The user's config
+fn vtodo_to_task(
+    vtodo: &BTreeMap<String, (String, String)>,
+    config: &Config,
+) -> Result<Result<Task, String>, ImportErrors> {
+    let property = |name: &str| vtodo.get(name).map(|(_, value)| value.as_str());
+
+    let status = match property("STATUS").unwrap_or("NEEDS-ACTION") {
+        "NEEDS-ACTION" => TaskStatus::NotStarted,
+        "IN-PROCESS" => TaskStatus::InProgress,
+        "COMPLETED" => TaskStatus::Completed,
+        "CANCELLED" => TaskStatus::Cancelled,
+        other => return Ok(Err(other.to_owned())),
+    };
+
+    let mut categories: Vec<String> = property("CATEGORIES")
+        .map(|categories| {
+            split_unescaped_commas(categories)
+                .iter()
+                .map(|category| unescape_text(category))
+                .filter(|category| !category.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let list = match categories.is_empty() {
+        true => config.current_list(),
+        false => categories.remove(0),
+    };
+
+    let description = unescape_text(property("SUMMARY").unwrap_or_default());
+
+    let mut task = match Task::new(description, status, list) {
+        Ok(task) => task,
+        Err(err) => return Err(ImportErrors::TaskErrors(err)),
+    };
+
+    task.set_tags(categories);
+
+    if let Some(uid) = property("UID") {
+        task.set_uid(unescape_text(uid));
+    }
+
+    task.set_created(property("CREATED").and_then(parse_date_time));
+
+    task.set_completed(property("COMPLETED").and_then(parse_date_time));
+
+    // Due dates can either be a date or a time, only the date is kept
+    task.set_due(property("DUE").and_then(|due| {
+        NaiveDate::parse_from_str(due, DATE_FORMAT)
+            .ok()
+            .or_else(|| parse_date_time(due).map(|due| due.date_naive()))
+    }));
+
+    task.set_priority(
+        match property("PRIORITY").and_then(|priority| priority.parse::<u8>().ok()) {
+            Some(1..=4) => Some(Priority::High),
+            Some(5) => Some(Priority::Medium),
+            Some(6..=9) => Some(Priority::Low),
+            _ => None,
+        },
+    );
+
+    Ok(Ok(task))
+}
+
+/// Writes a content line to the calendar, folding it if it is too long and ending it with the CRLF
+/// icalendar requires
+///
+/// Parameters
+/// calendar:   The calendar to write the line to
+/// line:       The line to write
+fn write_line(calendar: &mut String, line: &str) {
+    let mut length = 0;
+
+    for character in line.chars() {
+        // Folding before the limit is passed, taking into account the space that starts the
+        // folded line
+        if length + character.len_utf8() > MAX_LINE_LENGTH {
+            calendar.push_str("\r\n ");
+            length = 1;
+        }
+
+        calendar.push(character);
+        length += character.len_utf8();
+    }
+
+    write!(calendar, "\r\n").unwrap();
+}
+
+/// Joins any folded lines back together, returning the unfolded lines
+///
+/// Parameters
+/// calendar:   The contents of the icalendar file
+fn unfold_lines(calendar: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in calendar.lines() {
+        let line = line.trim_end_matches('\r');
+
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last_line)) => last_line.push_str(continuation),
+            _ => lines.push(line.to_owned()),
+        }
+    }
+
+    lines
+}
+
+/// Escapes the characters icalendar gives a special meaning to in text
+///
+/// Parameters
+/// text:   The text to escape
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Reverses escape_text
+///
+/// Parameters
+/// text:   The text to unescape
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::new();
+
+    let mut characters = text.chars();
+
+    while let Some(character) = characters.next() {
+        if character != '\\' {
+            unescaped.push(character);
+            continue;
+        }
+
+        match characters.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(escaped) => unescaped.push(escaped),
+            None => (),
+        }
+    }
+
+    unescaped
+}
+
+/// Splits a list of values on the commas that aren't escaped
+///
+/// Parameters
+/// values:   The comma separated values
+fn split_unescaped_commas(values: &str) -> Vec<String> {
+    let mut split: Vec<String> = vec![String::new()];
+
+    let mut characters = values.chars();
+
+    while let Some(character) = characters.next() {
+        match character {
+            ',' => split.push(String::new()),
+            '\\' => {
+                // Keeping the escape so that unescape_text can handle it
+                let last = split.last_mut().unwrap();
+                last.push('\\');
+                if let Some(escaped) = characters.next() {
+                    last.push(escaped);
+                }
+            }
+            _ => split.last_mut().unwrap().push(character),
+        }
+    }
+
+    split
+}
+
+/// Parses a time in icalendar's UTC format, returning None if it is invalid
+///
+/// Parameters
+/// date_time:   The time to parse
+fn parse_date_time(date_time: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(date_time, DATE_TIME_FORMAT)
+        .ok()
+        .map(|date_time| date_time.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::task_management;
+
+    #[test]
+    /// Tests if export_tasks writes a VTODO with the right properties
+    fn export_tasks_works() {
+        let mut task = Task::new(
+            String::from("Write the docs, then review them"),
+            TaskStatus::InProgress,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        task.set_uid(String::from("20230101T120000-0"));
+        task.set_due(NaiveDate::from_ymd_opt(2023, 1, 5));
+        task.set_tags(vec![String::from("docs")]);
+
+        let calendar = export_tasks(&[task], &[]);
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.contains("\r\nUID:20230101T120000-0\r\n"));
+        assert!(calendar.contains("\r\nSUMMARY:Write the docs\\, then review them\r\n"));
+        assert!(calendar.contains("\r\nSTATUS:IN-PROCESS\r\n"));
+        assert!(calendar.contains("\r\nCATEGORIES:Main,docs\r\n"));
+        assert!(calendar.contains("\r\nDUE;VALUE=DATE:20230105\r\n"));
+        assert!(calendar.ends_with("END:VTODO\r\nEND:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    /// Tests if long lines are folded so that no line is longer than the limit
+    fn export_tasks_folds_long_lines() {
+        let task = Task::new(
+            "a".repeat(200),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        let calendar = export_tasks(&[task], &[]);
+
+        assert!(calendar
+            .split("\r\n")
+            .all(|line| line.len() <= MAX_LINE_LENGTH));
+    }
+
+    #[test]
+    /// Tests if import_tasks reads VTODOs, including folded lines and lists from categories
+    fn import_tasks_works() {
+        let mut config = Config::new();
+
+        let calendar = "BEGIN:VCALENDAR\r\n\
+                        VERSION:2.0\r\n\
+                        BEGIN:VTODO\r\n\
+                        UID:abc@example.com\r\n\
+                        SUMMARY:Water the\r\n  plants\r\n\
+                        STATUS:COMPLETED\r\n\
+                        CATEGORIES:Home,garden\r\n\
+                        DUE:20230105T090000Z\r\n\
+                        PRIORITY:1\r\n\
+                        END:VTODO\r\n\
+                        BEGIN:VTODO\r\n\
+                        SUMMARY:Cancelled task\r\n\
+                        STATUS:CANCELLED\r\n\
+                        END:VTODO\r\n\
+                        BEGIN:VTODO\r\n\
+                        SUMMARY:Waiting task\r\n\
+                        STATUS:X-WAITING\r\n\
+                        END:VTODO\r\n\
+                        END:VCALENDAR\r\n";
+
+        let (tasks, notes) = import_tasks(calendar, &mut config).unwrap();
+
+        assert_eq!(notes, vec!["Skipped 1 x-waiting task"]);
+
+        assert!(config.is_valid_list(&String::from("Home")));
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1].status(), TaskStatus::Cancelled);
+        assert_eq!(tasks[0].uid(), "abc@example.com");
+        assert_eq!(tasks[0].description(), "Water the plants");
+        assert_eq!(tasks[0].status(), TaskStatus::Completed);
+        assert_eq!(tasks[0].list(), "Home");
+        assert_eq!(tasks[0].tags(), vec!["garden"]);
+        assert_eq!(tasks[0].due(), NaiveDate::from_ymd_opt(2023, 1, 5));
+        assert_eq!(tasks[0].priority(), Some(Priority::High));
+    }
+
+    #[test]
+    /// Tests if importing an export gives back the same tasks
+    fn import_tasks_reads_export() {
+        let mut config = Config::new();
+
+        let mut task = Task::new(
+            String::from("A task; with\nspecial characters, and a long description to fold"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        task.set_uid(String::from("20230101T120000-0"));
+        task.set_created(parse_date_time("20230101T120000Z"));
+        task.set_priority(Some(Priority::Medium));
+
+        let calendar = export_tasks(&[task.clone()], &[]);
+
+        let (tasks, _) = import_tasks(&calendar, &mut config).unwrap();
+
+        assert_eq!(tasks, vec![task])
+    }
+
+    #[test]
+    /// Tests if importing an export of the same tasks updates them instead of adding copies
+    fn import_tasks_round_trip_updates_tasks() {
+        let mut config = Config::new();
+
+        let mut tasks = vec![Task::new(
+            String::from("A task"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap()];
+
+        tasks[0].set_uid(String::from("20230101T120000-0"));
+        tasks[0].set_created(parse_date_time("20230101T120000Z"));
+
+        let mut changed = tasks[0].clone();
+        changed
+            .update_description(String::from("A changed task"))
+            .unwrap();
+
+        let calendar = export_tasks(&[changed.clone()], &[]);
+
+        let (imported, _) = import_tasks(&calendar, &mut config).unwrap();
+
+        let mut existing: Vec<&mut Task> = tasks.iter_mut().collect();
+
+        let new_tasks = task_management::update_matching_tasks(&mut existing, imported);
+
+        assert!(new_tasks.is_empty());
+        assert_eq!(tasks, vec![changed]);
+    }
+
+    #[test]
+    /// Tests if import_tasks returns the right error when not given a calendar
+    fn import_tasks_fails_on_invalid_calendar() {
+        let mut config = Config::new();
+
+        let error = import_tasks("- [ ] A markdown task", &mut config).unwrap_err();
+
+        assert_eq!(error, ImportErrors::InvalidFormat)
+    }
+}
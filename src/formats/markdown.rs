@@ -0,0 +1,338 @@
+use crate::task::{Task, TaskStatus};
+
+use crate::config::Config;
+
+use crate::formats::ImportErrors;
+
+use std::fmt::Write;
+
+/// The marker added after the description of tasks that are in progress, as markdown checklists
+/// only have a checked and unchecked state
+const IN_PROGRESS_MARKER: &str = "_(in progress)_";
+
+/// The marker added after the description of tasks that are blocked
+const BLOCKED_MARKER: &str = "_(blocked)_";
+
+/// Renders every list as a markdown heading followed by a checklist of its tasks
+///
+/// Parameters
+/// tasks:          The vec of tasks in the active list
+/// other_tasks:    The vec containing tasks not currently in the active list
+/// config:         The user's config
+pub fn export_tasks(tasks: &[Task], other_tasks: &[Task], config: &Config) -> String {
+    let mut markdown = String::new();
+
+    // Looping through the lists in the same order as list_all_tasks
+    for list in config.lists_iter() {
+        writeln!(markdown, "## {list}\n").unwrap();
+
+        for task in tasks.iter().chain(other_tasks.iter()) {
+            if &task.list() != list {
+                continue;
+            }
+
+            match task.status() {
+                TaskStatus::Completed => writeln!(markdown, "- [x] {}", task.description()),
+                TaskStatus::InProgress => writeln!(
+                    markdown,
+                    "- [ ] {} {}",
+                    task.description(),
+                    IN_PROGRESS_MARKER
+                ),
+                TaskStatus::NotStarted => writeln!(markdown, "- [ ] {}", task.description()),
+                // Cancelled tasks are checked off and struck through, as is common in checklists
+                TaskStatus::Cancelled => writeln!(markdown, "- [x] ~~{}~~", task.description()),
+                TaskStatus::Blocked => {
+                    writeln!(markdown, "- [ ] {} {}", task.description(), BLOCKED_MARKER)
+                }
+                // Statuses from the workflow are named in a marker like the built in ones
+                TaskStatus::Custom(name) => writeln!(
+                    markdown,
+                    "- [{}] {} _({})_",
+                    match task.is_finished() {
+                        true => 'x',
+                        false => ' ',
+                    },
+                    task.description(),
+                    name
+                ),
+            }
+            .unwrap();
+        }
+
+        writeln!(markdown).unwrap();
+    }
+
+    markdown
+}
+
+/// Reads the tasks out of a markdown checklist. Headings start a new list, which is added to the
+/// config if it doesn't exist yet, and any items before the first heading go into the current
+/// list. Lines that aren't checklist items are ignored
+///
+/// Parameters
+/// markdown:   The markdown to read the tasks from
+/// config:     The user's config
+pub fn import_tasks(markdown: &str, config: &mut Config) -> Result<Vec<Task>, ImportErrors> {
+    let mut tasks: Vec<Task> = Vec::new();
+
+    let mut list = config.current_list();
+
+    for line in markdown.lines() {
+        let line = line.trim();
+
+        if line.starts_with('#') {
+            let heading = line.trim_start_matches('#').trim();
+
+            if heading.is_empty() {
+                continue;
+            }
+
+            list = heading.to_owned();
+            continue;
+        }
+
+        let (description, status) = match parse_checklist_item(line, config) {
+            Some(item) => item,
+            None => continue,
+        };
+
+        let done = config.status_is_done(&status);
+
+        let mut task = match Task::new(description, status.clone(), list.clone()) {
+            Ok(task) => task,
+            Err(err) => return Err(ImportErrors::TaskErrors(err)),
+        };
+
+        // Custom statuses are only finished if they were given a completion time
+        if let TaskStatus::Custom(_) = status {
+            task.update_status_as(status, done);
+        }
+
+        tasks.push(task);
+    }
+
+    if tasks.is_empty() {
+        return Err(ImportErrors::NoTasksFound);
+    }
+
+    super::add_lists(&tasks, config);
+
+    Ok(tasks)
+}
+
+/// Parses a single checklist item, returning its description and status, or None if the line
+/// isn't a checklist item
+///
+/// Parameters
+/// line:     The trimmed line to parse
+/// config:   The user's config, for the statuses in the workflow
+fn parse_checklist_item(line: &str, config: &Config) -> Option<(String, TaskStatus)> {
+    let item = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))?;
+
+    let (checked, description) = if let Some(description) = item.strip_prefix("[ ]") {
+        (false, description)
+    } else if let Some(description) = item
+        .strip_prefix("[x]")
+        .or_else(|| item.strip_prefix("[X]"))
+    {
+        (true, description)
+    } else {
+        return None;
+    };
+
+    let description = description.trim();
+
+    let workflow_status = description
+        .strip_suffix(")_")
+        .and_then(|description| description.rsplit_once("_("))
+        .and_then(|(description, name)| match config.find_status(name) {
+            Some((status @ TaskStatus::Custom(_), _)) => Some((description, status)),
+            _ => None,
+        });
+
+    if let Some((description, status)) = workflow_status {
+        return Some((description.trim_end().to_owned(), status));
+    }
+
+    if checked {
+        return match description
+            .strip_prefix("~~")
+            .and_then(|description| description.strip_suffix("~~"))
+        {
+            Some(description) => Some((description.to_owned(), TaskStatus::Cancelled)),
+            None => Some((description.to_owned(), TaskStatus::Completed)),
+        };
+    }
+
+    if let Some(description) = description.strip_suffix(BLOCKED_MARKER) {
+        return Some((description.trim_end().to_owned(), TaskStatus::Blocked));
+    }
+
+    match description.strip_suffix(IN_PROGRESS_MARKER) {
+        Some(description) => Some((description.trim_end().to_owned(), TaskStatus::InProgress)),
+        None => Some((description.to_owned(), TaskStatus::NotStarted)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::task::TaskErrors;
+
+    #[test]
+    /// Tests if export_tasks renders each list as a heading with its tasks as checklist items
+    fn export_tasks_works() {
+        let mut config = Config::new();
+
+        config.add_list(String::from("Dev")).unwrap();
+
+        let tasks = vec![
+            Task::new(
+                String::from("A basic task"),
+                TaskStatus::Completed,
+                String::from("Main"),
+            )
+            .unwrap(),
+            Task::new(
+                String::from("Another basic task"),
+                TaskStatus::InProgress,
+                String::from("Main"),
+            )
+            .unwrap(),
+        ];
+
+        let other_tasks = vec![Task::new(
+            String::from("Yet another basic task"),
+            TaskStatus::NotStarted,
+            String::from("Dev"),
+        )
+        .unwrap()];
+
+        assert_eq!(
+            export_tasks(&tasks, &other_tasks, &config),
+            "## Main\n\n\
+             - [x] A basic task\n\
+             - [ ] Another basic task _(in progress)_\n\n\
+             ## Dev\n\n\
+             - [ ] Yet another basic task\n\n"
+        )
+    }
+
+    #[test]
+    /// Tests if import_tasks reads the tasks and their statuses, creating lists from headings
+    fn import_tasks_works() {
+        let mut config = Config::new();
+
+        let markdown = "- [ ] A basic task\n\
+                        # Dev\n\
+                        Some text that isn't a task\n\
+                        - [x] Another basic task\n\
+                        * [ ] Yet another basic task _(in progress)_\n";
+
+        let tasks = import_tasks(markdown, &mut config).unwrap();
+
+        assert_eq!(
+            tasks,
+            vec![
+                Task::new(
+                    String::from("A basic task"),
+                    TaskStatus::NotStarted,
+                    String::from("Main"),
+                )
+                .unwrap(),
+                Task::new(
+                    String::from("Another basic task"),
+                    TaskStatus::Completed,
+                    String::from("Dev"),
+                )
+                .unwrap(),
+                Task::new(
+                    String::from("Yet another basic task"),
+                    TaskStatus::InProgress,
+                    String::from("Dev"),
+                )
+                .unwrap(),
+            ]
+        );
+
+        assert!(config.is_valid_list(&String::from("Dev")));
+    }
+
+    #[test]
+    /// Tests if importing an export gives back the same tasks
+    fn import_tasks_reads_export() {
+        let mut config = Config::new();
+
+        let tasks = vec![
+            Task::new(
+                String::from("A basic task"),
+                TaskStatus::Completed,
+                String::from("Main"),
+            )
+            .unwrap(),
+            Task::new(
+                String::from("Another basic task"),
+                TaskStatus::InProgress,
+                String::from("Main"),
+            )
+            .unwrap(),
+            Task::new(
+                String::from("A cancelled task"),
+                TaskStatus::Cancelled,
+                String::from("Main"),
+            )
+            .unwrap(),
+            Task::new(
+                String::from("A blocked task"),
+                TaskStatus::Blocked,
+                String::from("Main"),
+            )
+            .unwrap(),
+        ];
+
+        let markdown = export_tasks(&tasks, &[], &config);
+
+        assert_eq!(import_tasks(&markdown, &mut config).unwrap(), tasks)
+    }
+
+    #[test]
+    /// Tests if import_tasks returns the right error when there are no checklist items
+    fn import_tasks_fails_when_no_tasks_found() {
+        let mut config = Config::new();
+
+        let error = import_tasks("# Dev\nJust some text", &mut config).unwrap_err();
+
+        assert_eq!(error, ImportErrors::NoTasksFound)
+    }
+
+    #[test]
+    /// Tests if import_tasks returns the right error when a checklist item has no description
+    fn import_tasks_fails_on_empty_description() {
+        let mut config = Config::new();
+
+        let error = import_tasks("- [ ] ", &mut config).unwrap_err();
+
+        assert_eq!(
+            error,
+            ImportErrors::TaskErrors(TaskErrors::EmptyDescription)
+        )
+    }
+
+    #[test]
+    /// Tests if a failed import doesn't add the lists from the headings read before the error
+    fn import_tasks_only_adds_lists_on_success() {
+        let mut config = Config::new();
+
+        assert!(import_tasks("# Dev\n- [ ] A dev task\n- [ ] ", &mut config).is_err());
+        assert!(!config.is_valid_list(&String::from("Dev")));
+
+        import_tasks("# Dev\n- [ ] A dev task", &mut config).unwrap();
+
+        assert!(config.is_valid_list(&String::from("Dev")));
+    }
+}
@@ -0,0 +1,473 @@
+use crate::task::{Priority, Task, TaskStatus};
+
+use crate::config::Config;
+
+use crate::formats::ImportErrors;
+
+use serde::{Deserialize, Serialize};
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use std::collections::BTreeMap;
+
+/// The format taskwarrior uses for dates, which are always in UTC
+const DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Fields taskwarrior keeps for its own bookkeeping, which have no meaning once imported and so
+/// aren't reported as unmapped
+const BOOKKEEPING_FIELDS: [&str; 4] = ["id", "uuid", "urgency", "modified"];
+
+/// Struct representing a task in the output of taskwarrior's `task export`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TaskwarriorTask {
+    description: String,
+    status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    start: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    end: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    due: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    /// A user defined attribute holding the statuses taskwarrior has no equivalent for, such as
+    /// blocked and custom statuses, so that they survive being exported and imported again
+    #[serde(
+        default,
+        rename = "todostatus",
+        skip_serializing_if = "Option::is_none"
+    )]
+    todo_status: Option<String>,
+    /// Any fields that todo has no equivalent for
+    #[serde(flatten, skip_serializing)]
+    other: BTreeMap<String, serde_json::Value>,
+}
+
+/// Exports every task as a taskwarrior json array, with lists becoming projects
+///
+/// Parameters
+/// tasks:          The vec of tasks in the active list
+/// other_tasks:    The vec containing tasks not currently in the active list
+pub fn export_tasks(tasks: &[Task], other_tasks: &[Task]) -> String {
+    let now = Utc::now();
+
+    let taskwarrior_tasks: Vec<TaskwarriorTask> = tasks
+        .iter()
+        .chain(other_tasks.iter())
+        .map(|task| {
+            let status = match task.status() {
+                TaskStatus::Completed => "completed",
+                // Taskwarrior has no cancelled status, its deleted tasks are the closest match
+                TaskStatus::Cancelled => "deleted",
+                // Custom statuses that count as done are completed
+                TaskStatus::Custom(_) if task.is_finished() => "completed",
+                _ => "pending",
+            };
+
+            // Taskwarrior requires every task to have an entry date, and completed tasks to have
+            // an end date, so the current time is used when they aren't known
+            let end = match task.is_finished() {
+                true => Some(format_date(task.completed().unwrap_or(now))),
+                false => None,
+            };
+
+            // The status is exported as pending, completed or deleted above, so the real one is
+            // kept too
+            let todo_status = match task.status() {
+                TaskStatus::Cancelled | TaskStatus::Blocked | TaskStatus::Custom(_) => {
+                    Some(task.status().name())
+                }
+                _ => None,
+            };
+
+            let priority = task.priority().map(|priority| {
+                match priority {
+                    Priority::High => "H",
+                    Priority::Medium => "M",
+                    Priority::Low => "L",
+                }
+                .to_owned()
+            });
+
+            TaskwarriorTask {
+                description: task.description(),
+                status: status.to_owned(),
+                project: Some(task.list()),
+                entry: Some(format_date(task.created().unwrap_or(now))),
+                // Taskwarrior marks a task as active by giving it a start date
+                start: task.started().map(format_date),
+                end,
+                due: task
+                    .due()
+                    .map(|due| format_date(due.and_hms_opt(0, 0, 0).unwrap().and_utc())),
+                priority,
+                tags: task.tags(),
+                todo_status,
+                other: BTreeMap::new(),
+            }
+        })
+        .collect();
+
+    // Serializing a vec of plain structs can't fail
+    serde_json::to_string_pretty(&taskwarrior_tasks).unwrap() + "\n"
+}
+
+/// Reads the tasks out of a taskwarrior json export. Projects become lists, which are added to the
+/// config if they don't exist, and tasks without a project go into the current list. Along with
+/// the tasks a list of notes is returned, describing anything that couldn't be imported
+///
+/// Parameters
+/// json:     The taskwarrior export to read the tasks from
+/// config:   The user's config
+pub fn import_tasks(
+    json: &str,
+    config: &mut Config,
+) -> Result<(Vec<Task>, Vec<String>), ImportErrors> {
+    let taskwarrior_tasks: Vec<TaskwarriorTask> = match serde_json::from_str(json) {
+        Ok(taskwarrior_tasks) => taskwarrior_tasks,
+        Err(_) => return Err(ImportErrors::InvalidFormat),
+    };
+
+    let mut tasks: Vec<Task> = Vec::new();
+
+    // Counting how many tasks had each field that couldn't be mapped, so that it can be reported
+    // once per field instead of once per task
+    let mut unmapped_fields: BTreeMap<String, usize> = BTreeMap::new();
+
+    let mut skipped_tasks: BTreeMap<String, usize> = BTreeMap::new();
+
+    for mut taskwarrior_task in taskwarrior_tasks {
+        let status = match taskwarrior_task.status.as_str() {
+            // Statuses exported from todo that taskwarrior has no equivalent for are kept as is
+            _ if taskwarrior_task.todo_status.is_some() => {
+                TaskStatus::from(taskwarrior_task.todo_status.clone().unwrap_or_default())
+            }
+            "completed" => TaskStatus::Completed,
+            "pending" | "waiting" if taskwarrior_task.start.is_some() => TaskStatus::InProgress,
+            "pending" | "waiting" => TaskStatus::NotStarted,
+            // Deleted tasks and the templates of recurring tasks have no equivalent
+            other => {
+                *skipped_tasks.entry(other.to_owned()).or_default() += 1;
+                continue;
+            }
+        };
+
+        let list = taskwarrior_task
+            .project
+            .take()
+            .unwrap_or_else(|| config.current_list());
+
+        let mut task = match Task::new(taskwarrior_task.description.clone(), status, list) {
+            Ok(task) => task,
+            Err(err) => return Err(ImportErrors::TaskErrors(err)),
+        };
+
+        let mut unmapped = |field: &str| *unmapped_fields.entry(field.to_owned()).or_default() += 1;
+
+        if let Some(entry) = &taskwarrior_task.entry {
+            match parse_date(entry) {
+                Some(entry) => task.set_created(Some(entry)),
+                None => unmapped("entry"),
+            }
+        }
+
+        if let Some(start) = &taskwarrior_task.start {
+            match parse_date(start) {
+                Some(start) => task.set_started(Some(start)),
+                None => unmapped("start"),
+            }
+        }
+
+        if let Some(end) = &taskwarrior_task.end {
+            match parse_date(end) {
+                // Only completed tasks keep an end date, deleted tasks are skipped above
+                Some(end) => task.set_completed(Some(end)),
+                None => unmapped("end"),
+            }
+        }
+
+        if let Some(due) = &taskwarrior_task.due {
+            match parse_date(due) {
+                Some(due) => task.set_due(Some(due.date_naive())),
+                None => unmapped("due"),
+            }
+        }
+
+        if let Some(priority) = &taskwarrior_task.priority {
+            match priority.as_str() {
+                "H" => task.set_priority(Some(Priority::High)),
+                "M" => task.set_priority(Some(Priority::Medium)),
+                "L" => task.set_priority(Some(Priority::Low)),
+                _ => unmapped("priority"),
+            }
+        }
+
+        task.set_tags(taskwarrior_task.tags.clone());
+
+        for field in taskwarrior_task.other.keys() {
+            if !BOOKKEEPING_FIELDS.contains(&field.as_str()) {
+                unmapped(field);
+            }
+        }
+
+        tasks.push(task);
+    }
+
+    let mut notes: Vec<String> = Vec::new();
+
+    for (status, count) in skipped_tasks {
+        notes.push(format!(
+            "Skipped {} {} {}",
+            count,
+            status,
+            plural_tasks(count)
+        ));
+    }
+
+    for (field, count) in unmapped_fields {
+        notes.push(format!(
+            "Couldn't map the {} of {} {}",
+            field,
+            count,
+            plural_tasks(count)
+        ));
+    }
+
+    if tasks.is_empty() {
+        return Err(ImportErrors::NoTasksFound);
+    }
+
+    super::add_lists(&tasks, config);
+
+    Ok((tasks, notes))
+}
+
+/// Formats the given time in taskwarrior's date format
+///
+/// Parameters
+/// date:   The time to format
+fn format_date(date: DateTime<Utc>) -> String {
+    date.format(DATE_FORMAT).to_string()
+}
+
+/// Parses a date in taskwarrior's date format, returning None if it is invalid
+///
+/// Parameters
+/// date:   The date to parse
+fn parse_date(date: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(date, DATE_FORMAT)
+        .ok()
+        .map(|date| date.and_utc())
+}
+
+/// Returns task or tasks depending on the count, for the import notes
+///
+/// Parameters
+/// count:   The number of tasks
+fn plural_tasks(count: usize) -> &'static str {
+    if count == 1 {
+        return "task";
+    }
+
+    "tasks"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::date;
+
+    #[test]
+    /// Tests if import_tasks maps every supported field onto the task
+    fn import_tasks_maps_fields() {
+        let mut config = Config::new();
+
+        let json = r#"[
+            {
+                "id": 1,
+                "description": "Write the docs",
+                "entry": "20230101T120000Z",
+                "modified": "20230102T120000Z",
+                "status": "pending",
+                "start": "20230102T120000Z",
+                "project": "Dev",
+                "due": "20230105T000000Z",
+                "priority": "H",
+                "tags": ["docs", "cli"],
+                "uuid": "a360fc44-315c-4366-b70c-ea7e7520b749",
+                "urgency": 9.2
+            },
+            {
+                "id": 0,
+                "description": "Release",
+                "entry": "20230101T120000Z",
+                "end": "20230103T120000Z",
+                "status": "completed"
+            }
+        ]"#;
+
+        let (tasks, notes) = import_tasks(json, &mut config).unwrap();
+
+        assert!(notes.is_empty());
+
+        assert!(config.is_valid_list(&String::from("Dev")));
+
+        assert_eq!(tasks[0].description(), "Write the docs");
+        assert_eq!(tasks[0].status(), TaskStatus::InProgress);
+        assert_eq!(tasks[0].list(), "Dev");
+        assert_eq!(tasks[0].created(), parse_date("20230101T120000Z"));
+        assert_eq!(tasks[0].started(), parse_date("20230102T120000Z"));
+        assert_eq!(tasks[0].due(), Some(date(2023, 1, 5)));
+        assert_eq!(tasks[0].priority(), Some(Priority::High));
+        assert_eq!(tasks[0].tags(), vec!["docs", "cli"]);
+
+        assert_eq!(tasks[1].status(), TaskStatus::Completed);
+        assert_eq!(tasks[1].list(), config.current_list());
+        assert_eq!(tasks[1].completed(), parse_date("20230103T120000Z"));
+    }
+
+    #[test]
+    /// Tests if import_tasks reports fields it couldn't map and the tasks it skipped
+    fn import_tasks_reports_unmapped_fields() {
+        let mut config = Config::new();
+
+        let json = r#"[
+            {
+                "description": "Water the plants",
+                "status": "pending",
+                "annotations": [{"entry": "20230101T120000Z", "description": "The ferns too"}],
+                "priority": "X"
+            },
+            {
+                "description": "An old task",
+                "status": "deleted"
+            },
+            {
+                "description": "Pay the rent",
+                "status": "recurring"
+            }
+        ]"#;
+
+        let (tasks, notes) = import_tasks(json, &mut config).unwrap();
+
+        assert_eq!(tasks.len(), 1);
+
+        assert_eq!(
+            notes,
+            vec![
+                "Skipped 1 deleted task",
+                "Skipped 1 recurring task",
+                "Couldn't map the annotations of 1 task",
+                "Couldn't map the priority of 1 task",
+            ]
+        )
+    }
+
+    #[test]
+    /// Tests if import_tasks returns the right error when given invalid json
+    fn import_tasks_fails_on_invalid_json() {
+        let mut config = Config::new();
+
+        let error = import_tasks("{ not json", &mut config).unwrap_err();
+
+        assert_eq!(error, ImportErrors::InvalidFormat)
+    }
+
+    #[test]
+    /// Tests if importing an export gives back the same tasks
+    fn import_tasks_reads_export() {
+        let mut config = Config::new();
+
+        let mut task = Task::new(
+            String::from("A basic task"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        task.set_created(parse_date("20230101T120000Z"));
+        task.set_due(Some(date(2023, 1, 5)));
+        task.set_priority(Some(Priority::Low));
+        task.set_tags(vec![String::from("docs")]);
+
+        let json = export_tasks(&[task.clone()], &[]);
+
+        let (tasks, notes) = import_tasks(&json, &mut config).unwrap();
+
+        assert!(notes.is_empty());
+
+        assert_eq!(tasks, vec![task])
+    }
+
+    #[test]
+    /// Tests if only tasks that have been started are exported with a start date
+    fn export_tasks_writes_start_dates() {
+        let mut started = Task::new(
+            String::from("A started task"),
+            TaskStatus::InProgress,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        started.set_started(parse_date("20230102T120000Z"));
+
+        let not_started = Task::new(
+            String::from("A task"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        let json = export_tasks(&[started, not_started], &[]);
+
+        assert_eq!(json.matches("\"start\": \"20230102T120000Z\"").count(), 1);
+        assert_eq!(json.matches("\"start\"").count(), 1);
+    }
+
+    #[test]
+    /// Tests if the statuses taskwarrior has no equivalent for come back after an export
+    fn import_tasks_reads_exported_statuses() {
+        let mut config = Config::new();
+
+        let statuses = [
+            TaskStatus::Cancelled,
+            TaskStatus::Blocked,
+            TaskStatus::Custom(String::from("Review")),
+        ];
+
+        let tasks: Vec<Task> = statuses
+            .iter()
+            .map(|status| {
+                let mut task = Task::new(
+                    String::from("A task"),
+                    TaskStatus::NotStarted,
+                    String::from("Main"),
+                )
+                .unwrap();
+
+                task.update_status(status.clone());
+
+                task
+            })
+            .collect();
+
+        let json = export_tasks(&tasks, &[]);
+
+        assert!(json.contains("todostatus"));
+
+        let (imported, notes) = import_tasks(&json, &mut config).unwrap();
+
+        assert!(notes.is_empty());
+
+        let imported_statuses: Vec<TaskStatus> = imported.iter().map(Task::status).collect();
+
+        assert_eq!(imported_statuses, statuses);
+    }
+}
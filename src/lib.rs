@@ -1,34 +1,79 @@
 // Public facing modules
 pub mod args;
 pub mod config;
+pub mod dry_run;
+pub mod selector;
+pub mod sorting;
 pub mod task;
 
 // Private modules
+mod add_mode;
+mod archive;
+mod board;
+mod confirm;
+mod dates;
+mod editor;
+mod focus;
+mod formats;
 mod program_state;
+mod search;
+mod show;
+mod stats;
 mod task_management;
+mod time_tracking;
+mod tui;
+mod wrap;
+
+#[cfg(test)]
+mod test_helpers;
 
 use crate::args::{
-    CleanupCommand, ConfigCommand, DeleteCommand, FinishCommand, ListCommand, RestartCommand,
-    StartCommand, TasksCommand, UpdateCommand,
+    AddCommand, ArchiveCommand, BlockCommand, BoardCommand, CancelCommand, CleanupCommand,
+    ConfigCommand, DeleteCommand, EditCommand, ExportCommand, FileFormat, FinishCommand,
+    FocusCommand, ImportCommand, ListCommand, MoveCommand, MoveToEndCommand, NoteCommand,
+    PlanCommand, RepeatCommand, RestartCommand, SearchCommand, SetStatusCommand, ShowCommand,
+    ShowFormat, StartCommand, StatsCommand, StatsFormat, StopCommand, TasksCommand, TimeCommand,
+    TimeFormat, UnarchiveCommand, UpdateCommand,
 };
 
 use std::fs;
 
+use std::io::Read;
+
+use std::fmt::Write;
+
 use colored::Colorize;
 
-use crate::task::{Task, TaskErrors, TaskStatus};
+use chrono::{DateTime, Local, Utc};
+
+use crate::task::{Task, TaskErrors, TaskId, TaskStatus};
 
 use crate::config::{Config, ListErrors};
 
+use crate::selector::TaskSelector;
+
+use crate::sorting::SortOrder;
+
+use crate::time_tracking::TimeSession;
+
 use crate::task_management::{TaskManagementErrors, UpdateTaskErrors};
 
 use crate::program_state::{DeserializationErrors, SerializationErrors};
 
+use crate::formats::ImportErrors;
+
+use crate::editor::EditorErrors;
+
 /// Reads the tasks file and returns a Vec<Task>
-pub fn read_tasks_file() -> Result<Vec<Task>, &'static str> {
+///
+/// Parameters
+/// dry_run:   Whether to carry on without a tasks file instead of offering to create one
+pub fn read_tasks_file(dry_run: bool) -> Result<Vec<Task>, &'static str> {
     match program_state::deserialize_tasks() {
         Ok(tasks_vec) => Ok(tasks_vec),
         Err(err) => match err {
+            // A dry run doesn't create anything, so it works as if the tasks file was empty
+            DeserializationErrors::FailedToCreateReader if dry_run => Ok(Vec::new()),
             DeserializationErrors::FailedToCreateReader => {
                 // TODO refactor this
                 // Asking the user if they'd like to create a tasks file in the directory
@@ -72,13 +117,67 @@ pub fn write_tasks_file(tasks: Vec<Task>) -> Result<(), &'static str> {
     }
 }
 
+/// Reads the archive file and returns the archived tasks
+pub fn read_archive_file() -> Result<Vec<Task>, &'static str> {
+    match program_state::deserialize_archive() {
+        Ok(archive) => Ok(archive),
+        Err(_) => Err("Failed to read the archive file!"),
+    }
+}
+
+/// Writes the archived tasks to the archive file
+///
+/// Parameters
+/// archive:   The archived tasks to write
+pub fn write_archive_file(archive: Vec<Task>) -> Result<(), &'static str> {
+    match program_state::serialize_archive(archive) {
+        Ok(_) => Ok(()),
+        Err(err) => match err {
+            SerializationErrors::UnableToCreateWriter => Err("Failed to create the writer!"),
+            SerializationErrors::FailedToSerialize => Err("Failed to serialize the archive!"),
+            SerializationErrors::CouldntFlush => Err("Could not flush!"),
+        },
+    }
+}
+
+/// Reads the time file and returns the recorded time tracking sessions
+pub fn read_time_file() -> Result<Vec<TimeSession>, &'static str> {
+    match program_state::deserialize_sessions() {
+        Ok(sessions) => Ok(sessions),
+        Err(_) => Err("Failed to read the time file!"),
+    }
+}
+
+/// Writes the time tracking sessions to the time file
+///
+/// Parameters
+/// sessions:   The sessions to write
+pub fn write_time_file(sessions: Vec<TimeSession>) -> Result<(), &'static str> {
+    match program_state::serialize_sessions(sessions) {
+        Ok(_) => Ok(()),
+        Err(err) => match err {
+            SerializationErrors::UnableToCreateWriter => Err("Failed to create the writer!"),
+            SerializationErrors::FailedToSerialize => Err("Failed to serialize the time!"),
+            SerializationErrors::CouldntFlush => Err("Could not flush!"),
+        },
+    }
+}
+
+/// Stops the timers of any tasks that are no longer in an active status, so that finishing,
+/// cancelling, restarting or deleting a task stops timing it
+///
+/// Parameters
+/// sessions:   The recorded time tracking sessions
+/// tasks:      Every task
+/// config:     The user's config
+pub fn stop_idle_timers(sessions: &mut [TimeSession], tasks: &[Task], config: &Config) {
+    time_tracking::stop_idle_timers(sessions, tasks, config, Utc::now());
+}
+
 /// Reads the config file and returns a Config regardless of any errors
 pub fn read_config_file() -> Config {
-    match program_state::deserialize_config() {
-        Ok(config) => config,
-        // Currently any errors will just return a default Config, this might change later though
-        Err(_) => Config::new(),
-    }
+    // Currently any errors will just return a default Config, this might change later though
+    program_state::deserialize_config().unwrap_or_default()
 }
 
 /// Write the given Config to the config file
@@ -96,25 +195,50 @@ pub fn write_config_file(config: Config) -> Result<(), &'static str> {
     }
 }
 
-/// Deletes the .todo file and everything in it
-pub fn nuke_todo() -> &'static str {
-    // Asking the user if they'd like to create a tasks file in the directory
-    println!("Are you sure you want to delete todo in this directory? [y/N]");
-
-    let mut answer = String::new();
+/// Deletes the .todo file and everything in it, once the user confirms
+///
+/// Parameters
+/// assume_yes:   Whether to skip asking the user to confirm
+pub fn nuke_todo(assume_yes: bool) -> &'static str {
+    let details = vec![String::from(
+        "The .todo dir, with the config and the tasks in every list",
+    )];
+
+    if !confirm::confirm(
+        "Are you sure you want to delete todo in this directory?",
+        &details,
+        assume_yes,
+    ) {
+        return "";
+    }
 
-    std::io::stdin().read_line(&mut answer).unwrap();
+    match fs::remove_dir_all(".todo") {
+        Ok(_) => "The .todo dir has been nuked!",
+        Err(_) => "The .todo dir couldn't be nuked!",
+    }
+}
 
-    let answer = answer.trim().to_lowercase();
+/// Describes what a command would change in the tasks, archive, timers and config, for --dry-run
+///
+/// Parameters
+/// before:   The state before the command ran
+/// after:    The state as it would be written
+pub fn describe_dry_run(before: &dry_run::Snapshot, after: &dry_run::Snapshot) -> String {
+    let changes = dry_run::describe_changes(before, after);
 
-    if answer == "y" || answer == "yes" {
-        match fs::remove_dir_all(".todo") {
-            Ok(_) => return "The .todo dir has been nuked!",
-            Err(_) => return "The .todo dir couldn't be nuked!",
-        }
+    if changes.is_empty() {
+        return String::from("Nothing would be changed");
     }
 
-    ""
+    format!("Nothing was written, the changes would be:\n{}", changes.join("\n"))
+}
+
+/// Gives every task in the given vec that doesn't have a unique id one
+///
+/// Parameters
+/// tasks:   The vec of tasks to assign ids to
+pub fn assign_task_uids(tasks: &mut [Task]) {
+    task_management::assign_uids(tasks);
 }
 
 /// Consumes the given vec and returns two vecs of tasks, with the first one containing all the
@@ -139,28 +263,84 @@ pub fn filter_task_vec(task_vec: Vec<Task>, config: &Config) -> (Vec<Task>, Vec<
     (tagged_tasks, other_tasks)
 }
 
-/// Lists the tasks in the given vec
+/// Shows the agenda for today across every list, with the tasks in progress, due today or
+/// overdue, or planned for today
 ///
 /// Parameters
-/// tasks:       The task vec to list
-/// config:      The user's config
-/// arguments:   The arguments for the command from the cli
-pub fn list_tasks(
-    tasks: &mut Vec<Task>,
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+pub fn show_today(
+    tasks: &[Task],
     other_tasks: &[Task],
     config: &Config,
+) -> Result<String, &'static str> {
+    let today = Local::now().date_naive();
+
+    match task_management::list_today_tasks(
+        tasks,
+        other_tasks,
+        config,
+        &config.default_sort(),
+        today,
+    ) {
+        Ok(agenda) => Ok(agenda),
+        Err(_) => Err("Nothing is planned for today!"),
+    }
+}
+
+/// Moves the unfinished tasks planned for today or earlier on to tomorrow, in every list
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+pub fn reset_today(tasks: &mut [Task], other_tasks: &mut [Task]) -> &'static str {
+    let today = Local::now().date_naive();
+
+    let rolled = task_management::roll_planned_tasks(tasks, today)
+        + task_management::roll_planned_tasks(other_tasks, today);
+
+    match rolled {
+        0 => "No unfinished tasks are planned for today!",
+        1 => "Task has been moved to tomorrow!",
+        _ => "Tasks have been moved to tomorrow!",
+    }
+}
+
+/// Lists the tasks in the given vec, in the order given by --sort-by or else the config's
+/// default_sort
+///
+/// Parameters
+/// tasks:         The task vec to list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// arguments:     The arguments for the command from the cli
+pub fn list_tasks(
+    tasks: &mut [Task],
+    other_tasks: &mut [Task],
+    config: &Config,
     arguments: TasksCommand,
 ) -> Result<String, &'static str> {
-    // Seeing if the user wants to sort the current list
+    // Seeing if the user wants to sort and save the order of the current list, or every list
     if arguments.sort {
-        // Ignoring the error this produces as if the list is empty then the listing of the
-        // function will print the same error
-        task_management::sort_tasks(tasks).unwrap_or(());
+        let order = arguments.sort_by.clone().unwrap_or_else(SortOrder::by_status);
+
+        // Ignoring the errors this produces as if the lists are empty then the listing of the
+        // function will print the same error. Sorting the other lists together is fine as only
+        // the order of the tasks within each list matters
+        task_management::sort_tasks_by(tasks, &order, config).unwrap_or(());
+
+        if arguments.all {
+            task_management::sort_tasks_by(other_tasks, &order, config).unwrap_or(());
+        }
     }
 
+    // The order the tasks are shown in, which doesn't change their saved order
+    let order = arguments.sort_by.unwrap_or_else(|| config.default_sort());
+
     // Seeing if the user wants to list all lists
     if arguments.all {
-        match task_management::list_all_tasks(tasks, other_tasks, config) {
+        match task_management::list_all_tasks(tasks, other_tasks, config, &order) {
             Ok(list) => return Ok(list),
             Err(err) => match err {
                 // This is the only possible error
@@ -173,7 +353,15 @@ pub fn list_tasks(
         }
     }
 
-    match task_management::list_tasks(tasks, config) {
+    // Seeing if the user only wants the tasks that are ready to start
+    if arguments.ready {
+        return match task_management::list_ready_tasks(tasks, config, &order) {
+            Ok(list) => Ok(list),
+            Err(_) => Err("There are no tasks ready to start!"),
+        };
+    }
+
+    match task_management::list_tasks(tasks, config, &order) {
         Ok(list) => Ok(list),
         Err(err) => match err {
             // This is the only possible error
@@ -184,12 +372,185 @@ pub fn list_tasks(
     }
 }
 
+/// Renders the current list, or every list, as a board with a column for each status, fitted to
+/// the width of the terminal
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// arguments:     The arguments for the command from the cli
+pub fn show_board(
+    tasks: &[Task],
+    other_tasks: &[Task],
+    config: &Config,
+    arguments: BoardCommand,
+) -> Result<String, &'static str> {
+    let width = wrap::line_width(config);
+
+    if !arguments.all {
+        if tasks.is_empty() {
+            return Err("There are no tasks in the list!");
+        }
+
+        let mut output = String::new();
+
+        // Showing the list name in the same cases as the tasks command
+        if config.always_show_list_names() || (config.smart_list_names() && config.lists_len() > 1)
+        {
+            writeln!(output, "{}", config.current_list().bold()).unwrap();
+        }
+
+        output.push_str(&board::render_board(tasks, config, width));
+
+        return Ok(output);
+    }
+
+    if tasks.is_empty() && other_tasks.is_empty() {
+        return Err("There are no tasks in any list!");
+    }
+
+    let mut output = String::new();
+
+    for list in config.lists_iter() {
+        let list_tasks: Vec<Task> = tasks
+            .iter()
+            .chain(other_tasks.iter())
+            .filter(|task| &task.list() == list)
+            .cloned()
+            .collect();
+
+        if list_tasks.is_empty() {
+            continue;
+        }
+
+        writeln!(output, "{}", list.bold()).unwrap();
+        output.push_str(&board::render_board(&list_tasks, config, width));
+        writeln!(output).unwrap();
+    }
+
+    Ok(output)
+}
+
+/// Searches the tasks in every list, returning the matches ranked with the best first
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// arguments:     The arguments for the command from the cli
+pub fn search_tasks(
+    tasks: &[Task],
+    other_tasks: &[Task],
+    config: &Config,
+    arguments: SearchCommand,
+) -> Result<String, &'static str> {
+    let hits = search::search(tasks, other_tasks, config, &arguments.query);
+
+    if hits.is_empty() {
+        return Err("No tasks found!");
+    }
+
+    Ok(search::render_hits(&hits))
+}
+
+/// Shows the statistics for every list, along with how quickly tasks are being finished
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// archive:       The archived tasks
+/// config:        The user's config
+/// arguments:     The arguments for the command from the cli
+pub fn show_stats(
+    tasks: &[Task],
+    other_tasks: &[Task],
+    archive: &[Task],
+    config: &Config,
+    arguments: StatsCommand,
+) -> String {
+    let stats = stats::Stats::new(tasks, other_tasks, archive, config, Local::now());
+
+    match arguments.format {
+        StatsFormat::Text => stats.to_text(),
+        StatsFormat::Json => stats.to_json(),
+    }
+}
+
+/// Shows the archived tasks of the current list, or of every list, optionally only those matching
+/// a search
+///
+/// Parameters
+/// archive:     The archived tasks
+/// config:      The user's config
+/// arguments:   The arguments for the command from the cli
+pub fn show_archive(
+    archive: &[Task],
+    config: &Config,
+    arguments: ArchiveCommand,
+) -> Result<String, &'static str> {
+    let list = config.current_list();
+
+    let list = match arguments.all {
+        true => None,
+        false => Some(list.as_str()),
+    };
+
+    let output = archive::render_archive(archive, list, arguments.query.as_deref());
+
+    if output.is_empty() {
+        return Err("No archived tasks found!");
+    }
+
+    Ok(output)
+}
+
+/// Restores the task with the given archive number to its list, or to the current list if its
+/// list has since been deleted
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// archive:       The archived tasks
+/// config:        The user's config
+/// arguments:     The arguments for the command from the cli
+pub fn unarchive_task(
+    tasks: &mut Vec<Task>,
+    other_tasks: &mut Vec<Task>,
+    archive: &mut Vec<Task>,
+    config: &Config,
+    arguments: UnarchiveCommand,
+) -> &'static str {
+    if archive.is_empty() {
+        return "No archived tasks found!";
+    }
+
+    // Archive numbers start at 1, like Task IDs
+    if arguments.archive_id == 0 || arguments.archive_id > archive.len() {
+        return "Archived task doesn't exist!";
+    }
+
+    let mut task = archive.remove(arguments.archive_id - 1);
+
+    if !config.is_valid_list(&task.list()) {
+        task.set_list(config.current_list());
+    }
+
+    match task.list() == config.current_list() {
+        true => tasks.push(task),
+        false => other_tasks.push(task),
+    }
+
+    "Task restored!"
+}
+
 /// Sorts the tasks in the given vec
 ///
 /// Parameters
-/// tasks:   The task vec to sort
-pub fn sort_list(tasks: &mut Vec<Task>) -> Result<(), &'static str> {
-    match task_management::sort_tasks(tasks) {
+/// tasks:    The task vec to sort
+/// config:   The user's config
+pub fn sort_list(tasks: &mut [Task], config: &Config) -> Result<(), &'static str> {
+    match task_management::sort_tasks(tasks, config) {
         Ok(_) => Ok(()),
         Err(err) => match err {
             TaskManagementErrors::EmptyTasklist => Err("There are no tasks in the list!"),
@@ -198,27 +559,62 @@ pub fn sort_list(tasks: &mut Vec<Task>) -> Result<(), &'static str> {
     }
 }
 
-/// Removes any Completed task in the given vec
+/// Moves any Completed or Cancelled task in the given vec to the archive. Recurring tasks have
+/// their next occurrence created first, so that only the finished occurrence is archived
 ///
 /// Parameters
-/// tasks:   The task vec to sort
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// archive:       The archived tasks
+/// arguments:     The arguments for the command from the cli
+/// assume_yes:    Whether to skip asking the user to confirm
 pub fn cleanup_list(
     tasks: &mut Vec<Task>,
     other_tasks: &mut Vec<Task>,
+    archive: &mut Vec<Task>,
     arguments: CleanupCommand,
+    assume_yes: bool,
 ) -> &'static str {
-    // Removing completed tasks from the current list
-    tasks.retain(|task| task.status() != TaskStatus::Completed);
+    let today = Local::now().date_naive();
 
-    // Removing completed tasks from all the lists if the all flag is used
+    // Showing every task that would be archived before archiving any
+    let mut details: Vec<String> = tasks
+        .iter()
+        .filter(|task| task.is_finished())
+        .map(|task| format!("{}: {}", task.list(), task.description()))
+        .collect();
+
+    if arguments.all {
+        details.extend(
+            other_tasks
+                .iter()
+                .filter(|task| task.is_finished())
+                .map(|task| format!("{}: {}", task.list(), task.description())),
+        );
+    }
+
+    if details.is_empty() {
+        return "No Completed or Cancelled tasks to archive!";
+    }
+
+    if !confirm::confirm("Archive these tasks?", &details, assume_yes) {
+        return "Nothing was archived!";
+    }
+
+    // Archiving completed tasks from the current list
+    task_management::create_next_occurrences(tasks, today);
+    archive::archive_finished(tasks, archive);
+
+    // Archiving completed tasks from all the lists if the all flag is used
     if arguments.all {
-        other_tasks.retain(|task| task.status() != TaskStatus::Completed);
+        task_management::create_next_occurrences(other_tasks, today);
+        archive::archive_finished(other_tasks, archive);
 
         // Early returning a different message to print
-        return "Removed all Completed Tasks!";
+        return "Archived all Completed and Cancelled Tasks!";
     }
 
-    "Removed Completed Tasks from the current list!"
+    "Archived Completed and Cancelled Tasks from the current list!"
 }
 
 /// Creates a new task. This handles any errors and returns an appropriate error message
@@ -230,7 +626,7 @@ pub fn cleanup_list(
 pub fn new_task(arguments: String, config: &Config) -> Result<Task, &'static str> {
     let list = config.current_list();
 
-    let task = match Task::new(arguments, TaskStatus::NotStarted, list) {
+    let mut task = match Task::new(arguments, TaskStatus::NotStarted, list) {
         Ok(task) => task,
         Err(err) => match err {
             TaskErrors::EmptyDescription => {
@@ -240,51 +636,94 @@ pub fn new_task(arguments: String, config: &Config) -> Result<Task, &'static str
         },
     };
 
+    task.set_created(Some(Utc::now()));
+
     Ok(task)
 }
 
-/// Triggers the add_mode, which creates a prompt that allows the user to add task rapidly, by only
-/// having to type the tasks description
+/// Adds a task with the description in the arguments to the tasks vec, as a subtask of the task at
+/// the parent Task ID if one is given. Returns a message to show on success, or an error message
 ///
 /// Parameters
-/// tasks:    The task vec to add the tasks too
-/// config:   The user's config
-pub fn add_mode(tasks: &mut Vec<Task>, config: &Config) {
-    // Opening blurb
-    print_info("To add a task type the description and press enter, to exit type x");
+/// tasks:       The task vec to add the task to
+/// arguments:   The arguments for the command from the cli
+/// config:      The user's config
+pub fn add_task(
+    tasks: &mut Vec<Task>,
+    arguments: AddCommand,
+    config: &Config,
+) -> Result<&'static str, &'static str> {
+    let mut task = new_task(arguments.description.unwrap_or_default(), config)?;
 
-    // Beginning the loop
-    loop {
-        let mut description = String::new();
+    task.set_recurrence(arguments.repeat);
 
-        // Getting the user input
-        std::io::stdin().read_line(&mut description).unwrap();
+    let Some(parent) = arguments.parent else {
+        tasks.push(task);
+        return Ok("Task added!");
+    };
 
-        // Trimming the output
-        description = description.trim().to_string();
+    let index = match task_management::find_task(tasks, &parent) {
+        Ok(index) => index,
+        Err(_) => return Err("Parent task doesn't exist!"),
+    };
 
-        // Checking if the user wants to exit
-        if description.to_lowercase() == "x" {
-            if config.command_feedback() {
-                print_info("Exited!");
-            }
-            return;
-        }
+    task.set_parent(Some(tasks[index].uid()));
 
-        // Attempting to create the task, and if there is an error printing it and continuing to
-        // the next iteration of the loop
-        match new_task(description, config) {
-            Ok(task) => {
-                tasks.push(task);
-                if config.command_feedback() {
-                    print_info("Added task!");
-                }
-            }
-            Err(err) => {
-                print_info(err);
-                continue;
-            }
-        }
+    tasks.push(task);
+
+    Ok("Subtask added!")
+}
+
+/// Completes any tasks whose subtasks have all been completed, if the config's
+/// auto_complete_parents option is on
+///
+/// Parameters
+/// tasks:    The task vec to check
+/// config:   The user's config
+pub fn auto_complete_parents(tasks: &mut [Task], config: &Config) {
+    if config.auto_complete_parents() {
+        task_management::complete_parents(tasks);
+    }
+}
+
+/// Triggers the add_mode, which creates a prompt that allows the user to add task rapidly, by only
+/// having to type the tasks description. The prompt has line editing and history, and supports
+/// commands for switching lists, setting the status, priority and due date, and undoing
+///
+/// Parameters
+/// tasks:         The task vec to add the tasks too
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// dry_run:       Whether to leave the history file as it was
+pub fn add_mode(
+    tasks: &mut Vec<Task>,
+    other_tasks: &mut Vec<Task>,
+    config: &Config,
+    dry_run: bool,
+) {
+    if add_mode::run(tasks, other_tasks, config, dry_run).is_err() {
+        print_info("Add mode couldn't read from the terminal!");
+    }
+}
+
+/// Opens the full screen interface over the tasks, which runs until the user quits
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// sessions:      The recorded time tracking sessions
+/// config:        The user's config
+/// assume_yes:    Whether to delete tasks without asking the user to confirm
+pub fn run_ui(
+    tasks: &mut Vec<Task>,
+    other_tasks: &mut Vec<Task>,
+    sessions: &mut Vec<TimeSession>,
+    config: &mut Config,
+    assume_yes: bool,
+) -> Result<(), &'static str> {
+    match tui::run(tasks, other_tasks, sessions, config, assume_yes) {
+        Ok(_) => Ok(()),
+        Err(_) => Err("The interface couldn't be opened in this terminal!"),
     }
 }
 
@@ -300,16 +739,20 @@ pub fn print_info(message: &str) {
 /// Parameters
 /// tasks:       The task vec to delete from
 /// arguments:   The arguments for the command from the cli
-pub fn update_task(tasks: &mut Vec<Task>, arguments: UpdateCommand) -> &'static str {
-    // Converting the task_id to an index
-    let index = task_id_to_index(arguments.task_id);
+pub fn update_task(tasks: &mut [Task], arguments: UpdateCommand) -> &'static str {
+    // Finding the task with the task_id
+    let index = match task_management::find_task(tasks, &arguments.task_id) {
+        Ok(index) => index,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(_) => return "Task not found!",
+    };
 
     // Getting the description
     let mut description = arguments.new_description;
 
     // Appending the new description if the append flag was used
     if arguments.append {
-        description = tasks[index].description() + &description;
+        description = format!("{} {}", tasks[index].description(), description);
     }
 
     match task_management::update_task_description(tasks, index, description) {
@@ -328,146 +771,944 @@ pub fn update_task(tasks: &mut Vec<Task>, arguments: UpdateCommand) -> &'static
     }
 }
 
+/// Opens the task at the given id, or every task in the current list if the list flag is used, in
+/// the user's editor and applies the edits back to the tasks
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// arguments:     The arguments for the command from the cli
+pub fn edit_task(
+    tasks: &mut Vec<Task>,
+    other_tasks: &mut Vec<Task>,
+    config: &Config,
+    arguments: EditCommand,
+) -> &'static str {
+    if arguments.list {
+        return edit_list(tasks, config);
+    }
+
+    // The task_id is required by the cli when the list flag isn't used
+    let index = match arguments
+        .task_id
+        .map(|task_id| task_management::find_task(tasks, &task_id))
+    {
+        Some(Ok(index)) => index,
+        _ => return "Task doesn't exist!",
+    };
+
+    let document = editor::task_to_document(&tasks[index]);
+
+    let edited_task = match editor::edit_until_valid(document, |document| {
+        editor::document_to_task(document, &tasks[index], config)
+    }) {
+        Ok(Some(edited_task)) => edited_task,
+        Ok(None) => return "No changes made!",
+        Err(err) => return editor_error_message(err),
+    };
+
+    // Moving the task out of the current list if its list was changed, with its subtasks so they
+    // aren't left without a parent
+    if edited_task.list() != config.current_list() {
+        let list = edited_task.list();
+
+        tasks[index] = edited_task;
+
+        let indexes = task_management::with_subtasks(tasks, &[index]);
+
+        let mut moved: Vec<Task> = indexes
+            .into_iter()
+            .rev()
+            .map(|index| tasks.remove(index))
+            .collect();
+
+        moved.reverse();
+
+        for mut task in moved {
+            task.set_list(list.clone());
+            other_tasks.push(task);
+        }
+
+        return "Task updated and moved to another list!";
+    }
+
+    tasks[index] = edited_task;
+
+    "Task updated successfully!"
+}
+
+/// Opens every task in the current list in the user's editor, one task per line, and replaces the
+/// list with the edited tasks
+///
+/// Parameters
+/// tasks:    The task vec of the current list
+/// config:   The user's config
+fn edit_list(tasks: &mut Vec<Task>, config: &Config) -> &'static str {
+    let document = editor::list_to_document(tasks, &config.current_list());
+
+    let tree = task_management::task_tree(tasks);
+
+    let task_ids: Vec<TaskId> = tree.iter().map(|(task_id, _)| task_id.clone()).collect();
+
+    let list_lines = match editor::edit_until_valid(document, |document| {
+        editor::document_to_list(document, &task_ids, config)
+    }) {
+        Ok(Some(list_lines)) => list_lines,
+        Ok(None) => return "No changes made!",
+        Err(err) => return editor_error_message(err),
+    };
+
+    let mut edited_tasks: Vec<Task> = Vec::new();
+
+    for line in list_lines {
+        let mut task = match line.task_id {
+            // Every Task ID is checked to be in the tree when parsing the document
+            Some(task_id) => match tree.iter().find(|(other_id, _)| other_id == &task_id) {
+                Some((_, index)) => tasks[*index].clone(),
+                None => return "Task doesn't exist",
+            },
+            None => match new_task(line.description.clone(), config) {
+                Ok(task) => task,
+                Err(err) => return err,
+            },
+        };
+
+        // The description is checked to not be empty when parsing the document
+        task.update_description(line.description).unwrap_or(());
+
+        // Only updating the status if it changed, so that the completion time is kept
+        if task.status() != line.status {
+            let done = config.status_is_done(&line.status);
+
+            task.update_status_as(line.status, done);
+        }
+
+        edited_tasks.push(task);
+    }
+
+    *tasks = edited_tasks;
+
+    "List updated!"
+}
+
+/// Adds the text in the arguments to the notes of the task at the given id as a new line, opens
+/// them in the user's editor, or clears them. If none of these are asked for the notes are
+/// returned to be printed, otherwise None is returned
+///
+/// Parameters
+/// tasks:       The task vec the task belongs to
+/// arguments:   The arguments for the command from the cli
+pub fn note_task(
+    tasks: &mut [Task],
+    arguments: NoteCommand,
+) -> Result<Option<String>, &'static str> {
+    let index = match task_management::find_task(tasks, &arguments.task_id) {
+        Ok(index) => index,
+        Err(TaskManagementErrors::EmptyTasklist) => return Err("No tasks found!"),
+        Err(_) => return Err("Task doesn't exist!"),
+    };
+
+    let notes = tasks[index].notes();
+
+    if arguments.clear {
+        tasks[index].set_notes(None);
+        return Ok(None);
+    }
+
+    if arguments.edit {
+        let document = notes.unwrap_or_default();
+
+        return match editor::edit_until_valid(document, |document| {
+            Ok(editor::document_to_notes(document))
+        }) {
+            Ok(Some(notes)) => {
+                tasks[index].set_notes(notes);
+                Ok(None)
+            }
+            Ok(None) => Err("No changes made!"),
+            Err(err) => Err(editor_error_message(err)),
+        };
+    }
+
+    let Some(text) = arguments.text else {
+        return match notes {
+            Some(notes) => Ok(Some(notes)),
+            None => Err("Task doesn't have any notes!"),
+        };
+    };
+
+    if text.trim().is_empty() {
+        return Err("Notes cannot be empty!");
+    }
+
+    tasks[index].set_notes(Some(match notes {
+        Some(notes) => format!("{}\n{}", notes, text),
+        None => text,
+    }));
+
+    Ok(None)
+}
+
+/// Returns everything about the task at the given id, in the format given in the arguments
+///
+/// Parameters
+/// tasks:       The task vec the task belongs to
+/// arguments:   The arguments for the command from the cli
+pub fn show_task(tasks: &[Task], arguments: ShowCommand) -> Result<String, &'static str> {
+    let index = match task_management::find_task(tasks, &arguments.task_id) {
+        Ok(index) => index,
+        Err(TaskManagementErrors::EmptyTasklist) => return Err("No tasks found!"),
+        Err(_) => return Err("Task doesn't exist!"),
+    };
+
+    let details = show::TaskDetails::new(tasks, index);
+
+    Ok(match arguments.format {
+        ShowFormat::Text => details.to_text(),
+        ShowFormat::Json => details.to_json(),
+    })
+}
+
+/// Returns the message to show for the given EditorErrors
+///
+/// Parameters
+/// err:   The error to get the message for
+fn editor_error_message(err: EditorErrors) -> &'static str {
+    match err {
+        EditorErrors::CouldntCreateFile => "Couldn't create the file to edit!",
+        EditorErrors::EditorFailed => "Couldn't open your editor, is $EDITOR set?",
+        EditorErrors::CouldntReadFile => "Couldn't read the edited file!",
+    }
+}
+
 /// Deletes a task/s from the list. This function handles the errors and returns a str containing a
 /// message to print
 ///
 /// Parameters
-/// tasks:       The task vec to delete from
+/// tasks:        The task vec to delete from
+/// arguments:    The arguments for the command from the cli
+/// assume_yes:   Whether to skip asking the user to confirm
+pub fn delete_task(
+    tasks: &mut Vec<Task>,
+    arguments: DeleteCommand,
+    assume_yes: bool,
+) -> &'static str {
+    // Finding every task before deleting any, as deleting a task renumbers the ones after it
+    let indexs = match task_ids_to_indexes(tasks, &arguments.task_ids) {
+        Ok(indexs) => indexs,
+        Err(TaskManagementErrors::NoTasksSelected) => return "No tasks match!",
+        Err(_) => return "Task doesn't exist!",
+    };
+
+    // Subtasks are deleted along with their parent, so they are confirmed too
+    let indexs = task_management::with_subtasks(tasks, &indexs);
+
+    let details = confirm::describe_tasks(tasks, &indexs);
+
+    if !confirm::confirm("Delete these tasks?", &details, assume_yes) {
+        return "Nothing was deleted!";
+    }
+
+    if task_management::delete_tasks(tasks, &indexs).is_err() {
+        return "Task doesn't exist!";
+    }
+
+    // Returning different messages based on whether there were multiple tasks to delete
+    if indexs.len() > 1 {
+        return "Tasks deleted!";
+    }
+
+    "Task deleted!"
+}
+
+/// Moves the task at the given id to the given position among the tasks at its level
+///
+/// Parameters
+/// tasks:       The task vec the task belongs to
 /// arguments:   The arguments for the command from the cli
-pub fn delete_task(tasks: &mut Vec<Task>, arguments: DeleteCommand) -> &'static str {
-    // Sorting the indexes and removing duplicates
-    let indexs = sort_and_filter_task_ids(arguments.task_ids);
+pub fn move_task(tasks: &mut Vec<Task>, arguments: MoveCommand) -> &'static str {
+    if arguments.position == 0 {
+        return "Positions start at 1!";
+    }
+
+    move_task_to(tasks, &arguments.task_id, arguments.position, "Task moved!")
+}
+
+/// Moves the task at the given id to the top of the tasks at its level
+///
+/// Parameters
+/// tasks:       The task vec the task belongs to
+/// arguments:   The arguments for the command from the cli
+pub fn move_task_to_top(tasks: &mut Vec<Task>, arguments: MoveToEndCommand) -> &'static str {
+    move_task_to(tasks, &arguments.task_id, 1, "Task moved to the top!")
+}
+
+/// Moves the task at the given id to the bottom of the tasks at its level
+///
+/// Parameters
+/// tasks:       The task vec the task belongs to
+/// arguments:   The arguments for the command from the cli
+pub fn move_task_to_bottom(tasks: &mut Vec<Task>, arguments: MoveToEndCommand) -> &'static str {
+    // Positions past the last task move it to the end
+    move_task_to(tasks, &arguments.task_id, usize::MAX, "Task moved to the bottom!")
+}
+
+/// Moves the task at the given id to the given position, returning the message for success or
+/// the error
+///
+/// Parameters
+/// tasks:      The task vec the task belongs to
+/// task_id:    The Task ID of the task to move
+/// position:   The position to move the task to, starting at 1
+/// message:    The message to return once the task is moved
+fn move_task_to(
+    tasks: &mut Vec<Task>,
+    task_id: &TaskId,
+    position: usize,
+    message: &'static str,
+) -> &'static str {
+    let index = match task_management::find_task(tasks, task_id) {
+        Ok(index) => index,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(_) => return "Task doesn't exist",
+    };
+
+    match task_management::move_task(tasks, index, position) {
+        Ok(_) => message,
+        Err(_) => "Task doesn't exist",
+    }
+}
+
+/// Starts the tasks at the given ids in the given tasks vec and starts timing them. Tasks that are
+/// already in progress have their timer started again if it was stopped
+///
+/// Parameters
+/// tasks:       The task vec the tasks belongs to
+/// sessions:    The recorded time tracking sessions
+/// arguments:   The arguments for the command from the cli
+pub fn start_task(
+    tasks: &mut [Task],
+    sessions: &mut Vec<TimeSession>,
+    arguments: StartCommand,
+) -> &'static str {
+    // Finding the tasks, with duplicates removed
+    let indexes = match task_ids_to_indexes(tasks, &arguments.task_ids) {
+        Ok(indexes) => indexes,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(TaskManagementErrors::NoTasksSelected) => return "No tasks match!",
+        Err(_) => return "Task doesn't exist",
+    };
+
+    // A task already in progress can still have its timer started again if it was stopped
+    let (resuming, indexes): (Vec<usize>, Vec<usize>) = indexes
+        .into_iter()
+        .partition(|index| tasks[*index].status() == TaskStatus::InProgress);
+    let resuming: Vec<usize> = resuming
+        .into_iter()
+        .filter(|index| !time_tracking::is_running(sessions, &tasks[*index].uid()))
+        .collect();
+
+    // Checking every task before any of them are started
+    let indexes = match task_management::tasks_to_update(
+        tasks,
+        &indexes,
+        &TaskStatus::InProgress,
+        false,
+        arguments.force,
+        chooses_several(&arguments.task_ids),
+    ) {
+        Ok(indexes) => indexes,
+        Err(TaskManagementErrors::TaskAlreadyGivenStatus) if !resuming.is_empty() => Vec::new(),
+        Err(err) => {
+            // Generating the error message
+            let error_message = match err {
+                TaskManagementErrors::TaskAlreadyGivenStatus => "Task is already in progress!",
+                TaskManagementErrors::TaskDoesntExist => "Task doesn't exist",
+                TaskManagementErrors::EmptyTasklist => "No tasks found!",
+                TaskManagementErrors::TaskIsBlocked => {
+                    "Task is blocked, use --force to start it anyway"
+                }
+                _ => "Unknown error!",
+            };
+
+            return error_message;
+        }
+    };
+
+    for index in indexes.iter().copied() {
+        tasks[index].update_status(TaskStatus::InProgress);
+    }
+
+    for index in indexes.iter().chain(resuming.iter()).copied() {
+        time_tracking::start_timer(sessions, &tasks[index], Utc::now());
+    }
+
+    // Returning a success message with a plural if more than one task was started
+    if indexes.len() + resuming.len() > 1 {
+        return "Tasks have been started!";
+    }
+
+    "Tasks has been started!"
+}
+
+/// Stops timing the tasks at the given ids, or every task being timed if none are given, leaving
+/// the tasks in progress
+///
+/// Parameters
+/// tasks:       The task vec the tasks belongs to
+/// sessions:    The recorded time tracking sessions
+/// arguments:   The arguments for the command from the cli
+pub fn stop_task(
+    tasks: &[Task],
+    sessions: &mut [TimeSession],
+    arguments: StopCommand,
+) -> &'static str {
+    let uids: Vec<String> = match arguments.task_ids.is_empty() {
+        true => sessions
+            .iter()
+            .map(|session| session.task_uid.clone())
+            .collect(),
+        false => match task_ids_to_indexes(tasks, &arguments.task_ids) {
+            Ok(indexes) => indexes.iter().map(|index| tasks[*index].uid()).collect(),
+            Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+            Err(TaskManagementErrors::NoTasksSelected) => return "No tasks match!",
+            Err(_) => return "Task doesn't exist",
+        },
+    };
+
+    match time_tracking::stop_timers(sessions, &uids, Utc::now()) {
+        0 => "No timers are running!",
+        1 => "Timer stopped!",
+        _ => "Timers stopped!",
+    }
+}
+
+/// Reports the time spent on each task, from every list or only the given one, either printed or
+/// written to a file
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// archive:       The archived tasks
+/// sessions:      The recorded time tracking sessions
+/// arguments:     The arguments for the command from the cli
+/// dry_run:       Whether to return the report instead of writing it to the output file
+pub fn time_report(
+    tasks: &[Task],
+    other_tasks: &[Task],
+    archive: &[Task],
+    sessions: &[TimeSession],
+    arguments: TimeCommand,
+    dry_run: bool,
+) -> Result<Option<String>, &'static str> {
+    let since: Option<DateTime<Utc>> = match arguments.since {
+        Some(since) => {
+            let Some(date) = dates::parse_past_date(&since, Local::now().date_naive()) else {
+                return Err("Couldn't understand the date, use a date such as 2023-01-05");
+            };
+
+            // Counting from the start of the day in the user's timezone
+            date.and_hms_opt(0, 0, 0)
+                .and_then(|start| start.and_local_timezone(Local).earliest())
+                .map(|start| start.with_timezone(&Utc))
+        }
+        None => None,
+    };
+
+    let all_tasks: Vec<&Task> = tasks.iter().chain(other_tasks).chain(archive).collect();
+
+    let times = time_tracking::time_spent(
+        sessions,
+        &all_tasks,
+        arguments.list.as_deref(),
+        since,
+        Utc::now(),
+    );
+
+    if times.is_empty() {
+        return Err("No time has been tracked!");
+    }
+
+    let report = match arguments.format {
+        TimeFormat::Text => time_tracking::render_report(&times),
+        TimeFormat::Csv => time_tracking::render_csv(&times),
+    };
+
+    // A dry run shows what would have been written instead of writing it
+    match arguments.output.filter(|_| !dry_run) {
+        Some(path) => match fs::write(path, report) {
+            Ok(_) => Ok(None),
+            Err(_) => Err("Couldn't write the report!"),
+        },
+        None => Ok(Some(report)),
+    }
+}
 
-    // Looping through the given indexes in reverse order to remove them to prevent deleting the
-    // wrong items or attempting to delete at an index that is no longer in the bounds of the vec
-    for i in (0..indexs.len()).rev() {
-        // Converting the index to a task id
-        let index = task_id_to_index(indexs[i]);
+/// Starts the task at the given id and focuses on it for a pomodoro, timing it while the countdown
+/// runs. A completed pomodoro is recorded on the task, and the user is asked whether to finish it
+///
+/// Parameters
+/// tasks:       The task vec the task belongs to
+/// sessions:    The recorded time tracking sessions
+/// config:      The user's config, for the length of a pomodoro
+/// arguments:   The arguments for the command from the cli
+pub fn focus_task(
+    tasks: &mut Vec<Task>,
+    sessions: &mut Vec<TimeSession>,
+    config: &Config,
+    arguments: FocusCommand,
+) -> &'static str {
+    let index = match task_management::find_task(tasks, &arguments.task_id) {
+        Ok(index) => index,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(_) => return "Task doesn't exist",
+    };
+
+    let minutes = arguments.minutes.unwrap_or(config.pomodoro_minutes());
 
-        // Using an if let here as I don't care about the Ok variant
-        if task_management::delete_task(tasks, index).is_err() {
-            return "Task doesn't exist!";
+    if minutes == 0 {
+        return "A pomodoro must be at least a minute long!";
+    }
+
+    let Some(seconds) = minutes.checked_mul(60) else {
+        return "That pomodoro is far too long!";
+    };
+
+    // Without a terminal nobody can see the countdown or stop it, so it would just hang
+    if !focus::is_interactive() {
+        return "Focus needs a terminal to count down in!";
+    }
+
+    let result = task_management::update_task_status(
+        tasks,
+        index,
+        TaskStatus::InProgress,
+        arguments.force,
+    );
+
+    // Focusing on a task that is already in progress is fine
+    match result {
+        Ok(_) | Err(TaskManagementErrors::TaskAlreadyGivenStatus) => (),
+        Err(TaskManagementErrors::TaskIsBlocked) => {
+            return "Task is blocked, use --force to focus on it anyway"
         }
+        Err(_) => return "Unknown error!",
     }
 
-    // Returning different messages based on whether there were multiple tasks to delete
-    if indexs.len() > 1 {
-        return "Tasks deleted!";
+    // Only a timer started for the pomodoro is stopped after it, so one started before keeps going
+    let timing = time_tracking::start_timer(sessions, &tasks[index], Utc::now());
+
+    let completed = focus::countdown(
+        &tasks[index].description(),
+        std::time::Duration::from_secs(seconds),
+    );
+
+    if timing {
+        time_tracking::stop_timers(sessions, &[tasks[index].uid()], Utc::now());
+    }
+
+    match completed {
+        Some(true) => (),
+        Some(false) => return "Focus stopped early, the pomodoro wasn't counted",
+        None => return "Couldn't count down the pomodoro in this terminal!",
+    }
+
+    tasks[index].add_pomodoro();
+
+    // Ringing the terminal bell to let the user know the pomodoro is over
+    print!("\x07");
+
+    if !confirm::ask("Pomodoro complete! Finish the task?") {
+        return "Pomodoro recorded!";
+    }
+
+    if task_management::update_task_status(tasks, index, TaskStatus::Completed, arguments.force)
+        .is_err()
+    {
+        return "Pomodoro recorded, but the task couldn't be completed";
+    }
+
+    if task_management::create_next_occurrences(tasks, Local::now().date_naive()) > 0 {
+        return "Pomodoro recorded and completed, and the next occurrence has been added!";
+    }
+
+    "Pomodoro recorded and the task has been completed!"
+}
+
+/// Finishes the task at the given id in the given tasks vec, adding the next occurrence of any
+/// recurring tasks
+///
+/// Parameters
+/// tasks:       The task vec the tasks belongs to
+/// arguments:   The arguments for the command from the cli
+pub fn finish_task(tasks: &mut Vec<Task>, arguments: FinishCommand) -> &'static str {
+    // Finding the tasks, with duplicates removed
+    let indexes = match task_ids_to_indexes(tasks, &arguments.task_ids) {
+        Ok(indexes) => indexes,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(TaskManagementErrors::NoTasksSelected) => return "No tasks match!",
+        Err(_) => return "Task doesn't exist",
+    };
+
+    // Checking every task before any of them are completed
+    let indexes = match task_management::tasks_to_update(
+        tasks,
+        &indexes,
+        &TaskStatus::Completed,
+        true,
+        arguments.force,
+        chooses_several(&arguments.task_ids),
+    ) {
+        Ok(indexes) => indexes,
+        Err(err) => {
+            let error_message = match err {
+                TaskManagementErrors::TaskAlreadyGivenStatus => "Task is already completed",
+                TaskManagementErrors::TaskDoesntExist => "Task doesn't exist",
+                TaskManagementErrors::EmptyTasklist => "No tasks found!",
+                TaskManagementErrors::TaskIsBlocked => {
+                    "Task is blocked, use --force to finish it anyway"
+                }
+                _ => "Unknown error!",
+            };
+
+            return error_message;
+        }
+    };
+
+    for index in indexes.iter().copied() {
+        tasks[index].update_status(TaskStatus::Completed);
+    }
+
+    let occurrences = task_management::create_next_occurrences(tasks, Local::now().date_naive());
+
+    if occurrences > 0 {
+        return "Completed, and the next occurrence has been added!";
+    }
+
+    // Returning a success message with a plural if more than one task was completed
+    if indexes.len() > 1 {
+        return "Tasks have been completed!";
+    }
+
+    "Task has been completed!"
+}
+
+/// Restarts the task at the given id in the given tasks vec
+///
+/// Parameters
+/// tasks:       The task vec the tasks belongs to
+/// arguments:   The arguments for the command from the cli
+pub fn restart_task(tasks: &mut [Task], arguments: RestartCommand) -> &'static str {
+    // Finding the tasks, with duplicates removed
+    let indexes = match task_ids_to_indexes(tasks, &arguments.task_ids) {
+        Ok(indexes) => indexes,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(TaskManagementErrors::NoTasksSelected) => return "No tasks match!",
+        Err(_) => return "Task doesn't exist",
+    };
+
+    // Checking every task before any of them are restarted, which is allowed even if blocked
+    let indexes = match task_management::tasks_to_update(
+        tasks,
+        &indexes,
+        &TaskStatus::NotStarted,
+        false,
+        true,
+        chooses_several(&arguments.task_ids),
+    ) {
+        Ok(indexes) => indexes,
+        Err(err) => {
+            let error_message = match err {
+                TaskManagementErrors::TaskAlreadyGivenStatus => "Task is already Not Started",
+                TaskManagementErrors::TaskDoesntExist => "Task doesn't exist",
+                TaskManagementErrors::EmptyTasklist => "No tasks found!",
+                _ => "Unknown error!",
+            };
+
+            return error_message;
+        }
+    };
+
+    for index in indexes.iter().copied() {
+        tasks[index].update_status(TaskStatus::NotStarted);
+    }
+
+    // Returning a success message with a plural if more than one task was completed
+    if indexes.len() > 1 {
+        return "Tasks have been restarted!";
+    }
+
+    "Task has been restarted!"
+}
+
+/// Sets how often the task at the given id comes back once completed, or stops it coming back if
+/// no recurrence is given
+///
+/// Parameters
+/// tasks:       The task vec the task belongs to
+/// arguments:   The arguments for the command from the cli
+pub fn repeat_task(tasks: &mut [Task], arguments: RepeatCommand) -> &'static str {
+    let index = match task_management::find_task(tasks, &arguments.task_id) {
+        Ok(index) => index,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(_) => return "Task doesn't exist!",
+    };
+
+    tasks[index].set_recurrence(arguments.recurrence);
+
+    match arguments.recurrence {
+        Some(_) => "Task will come back once completed!",
+        None => "Task will no longer come back!",
+    }
+}
+
+/// Plans the tasks at the given ids for a day, today unless another is given, or removes them from
+/// the day they are planned for
+///
+/// Parameters
+/// tasks:       The task vec the tasks belongs to
+/// arguments:   The arguments for the command from the cli
+pub fn plan_task(tasks: &mut [Task], arguments: PlanCommand) -> &'static str {
+    let indexes = match task_ids_to_indexes(tasks, &arguments.task_ids) {
+        Ok(indexes) => indexes,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(TaskManagementErrors::NoTasksSelected) => return "No tasks match!",
+        Err(_) => return "Task doesn't exist",
+    };
+
+    let today = Local::now().date_naive();
+
+    let planned = match (arguments.clear, arguments.date) {
+        (true, _) => None,
+        (false, Some(date)) => match dates::parse_date(&date, today) {
+            Some(date) => Some(date),
+            None => return "Couldn't understand the date, use a date such as 2023-01-05",
+        },
+        (false, None) => Some(today),
+    };
+
+    for index in indexes.iter().copied() {
+        tasks[index].set_planned(planned);
     }
 
-    "Task deleted!"
+    match (planned, indexes.len() > 1) {
+        (None, false) => "Task is no longer planned!",
+        (None, true) => "Tasks are no longer planned!",
+        (Some(_), false) => "Task has been planned!",
+        (Some(_), true) => "Tasks have been planned!",
+    }
 }
 
-/// Starts the tasks at the given ids in the given tasks vec
+/// Cancels the tasks at the given ids in the given tasks vec. Cancelling a recurring task ends the
+/// series, so no next occurrence is added
 ///
 /// Parameters
 /// tasks:       The task vec the tasks belongs to
 /// arguments:   The arguments for the command from the cli
-pub fn start_task(tasks: &mut Vec<Task>, arguments: StartCommand) -> &'static str {
-    // Sorting the indexes and removing duplicates
-    let indexes = sort_and_filter_task_ids(arguments.task_ids);
-
-    for index in &indexes {
-        let index = task_id_to_index(*index);
+pub fn cancel_task(tasks: &mut [Task], arguments: CancelCommand) -> &'static str {
+    let indexes = match task_ids_to_indexes(tasks, &arguments.task_ids) {
+        Ok(indexes) => indexes,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(TaskManagementErrors::NoTasksSelected) => return "No tasks match!",
+        Err(_) => return "Task doesn't exist",
+    };
 
-        // Using an if let statement as only the Err variant is needed
-        if let Err(err) = task_management::update_task_status(tasks, index, TaskStatus::InProgress)
-        {
-            // Generating the error message
+    // Checking every task before any of them are cancelled, which is allowed even if blocked
+    let indexes = match task_management::tasks_to_update(
+        tasks,
+        &indexes,
+        &TaskStatus::Cancelled,
+        false,
+        true,
+        chooses_several(&arguments.task_ids),
+    ) {
+        Ok(indexes) => indexes,
+        Err(err) => {
             let error_message = match err {
-                TaskManagementErrors::TaskAlreadyGivenStatus => "Task is already in progress!",
+                TaskManagementErrors::TaskAlreadyGivenStatus => "Task is already cancelled",
                 TaskManagementErrors::TaskDoesntExist => "Task doesn't exist",
                 TaskManagementErrors::EmptyTasklist => "No tasks found!",
+                _ => "Unknown error!",
             };
 
             return error_message;
         }
+    };
+
+    for index in indexes.iter().copied() {
+        tasks[index].update_status(TaskStatus::Cancelled);
     }
 
-    // Returning a success message with a plural if more than one task was started
+    // Returning a success message with a plural if more than one task was cancelled
     if indexes.len() > 1 {
-        return "Tasks have been started!";
+        return "Tasks have been cancelled!";
     }
 
-    "Tasks has been started!"
+    "Task has been cancelled!"
 }
 
-/// Finishes the task at the given id in the given tasks vec
+/// Sets the status of the task at the given id to the named status, which can be a built in
+/// status or one from the config's workflow. Tasks given an active status start being timed
 ///
 /// Parameters
-/// tasks:       The task vec the tasks belongs to
-/// arguments:   The arguments for the command from the cli
-pub fn finish_task(tasks: &mut Vec<Task>, arguments: FinishCommand) -> &'static str {
-    // Sorting the indexes and removing duplicates
-    let indexes = sort_and_filter_task_ids(arguments.task_ids);
+/// tasks:       The task vec of the current list
+/// sessions:    The recorded time tracking sessions
+/// config:      The user's config
+/// arguments:   The arguments passed to the set-status command
+pub fn set_task_status(
+    tasks: &mut Vec<Task>,
+    sessions: &mut Vec<TimeSession>,
+    config: &Config,
+    arguments: SetStatusCommand,
+) -> &'static str {
+    let index = match task_management::find_task(tasks, &arguments.task_id) {
+        Ok(index) => index,
+        Err(TaskManagementErrors::EmptyTasklist) => return "No tasks found!",
+        Err(_) => return "Task doesn't exist",
+    };
 
-    for index in &indexes {
-        let index = task_id_to_index(*index);
+    let (status, done) = match config.find_status(&arguments.status) {
+        Some(status) => status,
+        None => return "That status doesn't exist, see the statuses with todo config",
+    };
 
-        if let Err(err) = task_management::update_task_status(tasks, index, TaskStatus::Completed) {
-            let error_message = match err {
-                TaskManagementErrors::TaskAlreadyGivenStatus => "Task is already completed",
-                TaskManagementErrors::TaskDoesntExist => "Task doesn't exist",
-                TaskManagementErrors::EmptyTasklist => "No tasks found!",
-            };
+    if let Err(err) =
+        task_management::update_task_status_as(tasks, index, status, done, arguments.force)
+    {
+        return match err {
+            TaskManagementErrors::TaskAlreadyGivenStatus => "Task already has that status!",
+            TaskManagementErrors::TaskIsBlocked => {
+                "Task is blocked, use --force to set the status anyway"
+            }
+            _ => "Unknown error!",
+        };
+    }
 
-            return error_message;
-        }
+    if config.status_is_active(&tasks[index].status()) {
+        time_tracking::start_timer(sessions, &tasks[index], Utc::now());
     }
 
-    // Returning a success message with a plural if more than one task was completed
-    if indexes.len() > 1 {
-        return "Tasks have been completed!";
+    let occurrences = task_management::create_next_occurrences(tasks, Local::now().date_naive());
+
+    if occurrences > 0 {
+        return "Status updated, and the next occurrence has been added!";
     }
 
-    "Task has been completed!"
+    "Status updated!"
 }
 
-/// Restarts the task at the given id in the given tasks vec
+/// Sets the status of the task at the given id to Blocked, or if a task to block on is given,
+/// blocks the task from being started until that task is completed
 ///
 /// Parameters
-/// tasks:       The task vec the tasks belongs to
+/// tasks:       The task vec the tasks belong to
 /// arguments:   The arguments for the command from the cli
-pub fn restart_task(tasks: &mut Vec<Task>, arguments: RestartCommand) -> &'static str {
-    // Sorting the indexes and removing duplicates
-    let indexes = sort_and_filter_task_ids(arguments.task_ids);
-
-    for index in &indexes {
-        let index = task_id_to_index(*index);
+pub fn block_task(tasks: &mut [Task], arguments: BlockCommand) -> &'static str {
+    let (index, blocker) = match find_task_pair(tasks, &arguments) {
+        Ok(indexes) => indexes,
+        Err(err) => return err,
+    };
 
-        if let Err(err) = task_management::update_task_status(tasks, index, TaskStatus::NotStarted)
+    let Some(blocker) = blocker else {
+        return match task_management::update_task_status(tasks, index, TaskStatus::Blocked, true)
         {
-            let error_message = match err {
-                TaskManagementErrors::TaskAlreadyGivenStatus => "Task is already Not Started",
-                TaskManagementErrors::TaskDoesntExist => "Task doesn't exist",
-                TaskManagementErrors::EmptyTasklist => "No tasks found!",
-            };
+            Ok(_) => "Task has been marked as blocked!",
+            Err(TaskManagementErrors::TaskAlreadyGivenStatus) => "Task is already blocked!",
+            Err(_) => "Task doesn't exist!",
+        };
+    };
 
-            return error_message;
+    match task_management::block_task(tasks, index, blocker) {
+        Ok(_) => "Task blocked!",
+        Err(TaskManagementErrors::DependencyCycle) => {
+            "Tasks can't block each other, that task is already waiting on this one!"
         }
+        Err(_) => "Task doesn't exist!",
     }
+}
 
-    // Returning a success message with a plural if more than one task was completed
-    if indexes.len() > 1 {
-        return "Tasks have been restarted!";
+/// Sets the status of the blocked task at the given id back to Not Started, or if a task to block
+/// on is given, stops the task being blocked by that task
+///
+/// Parameters
+/// tasks:       The task vec the tasks belong to
+/// arguments:   The arguments for the command from the cli
+pub fn unblock_task(tasks: &mut [Task], arguments: BlockCommand) -> &'static str {
+    let (index, blocker) = match find_task_pair(tasks, &arguments) {
+        Ok(indexes) => indexes,
+        Err(err) => return err,
+    };
+
+    let Some(blocker) = blocker else {
+        if tasks[index].status() != TaskStatus::Blocked {
+            return "Task isn't blocked!";
+        }
+
+        tasks[index].update_status(TaskStatus::NotStarted);
+
+        return "Task unblocked!";
+    };
+
+    match task_management::unblock_task(tasks, index, blocker) {
+        Ok(_) => "Task unblocked!",
+        Err(TaskManagementErrors::TaskNotBlocked) => "Task isn't blocked by that task!",
+        Err(_) => "Task doesn't exist!",
     }
+}
 
-    "Task has been restarted!"
+/// Finds the indexes of the blocked task and the task it is blocked on, if one was given, for the
+/// block commands
+///
+/// Parameters
+/// tasks:       The task vec the tasks belong to
+/// arguments:   The arguments for the command from the cli
+fn find_task_pair(
+    tasks: &[Task],
+    arguments: &BlockCommand,
+) -> Result<(usize, Option<usize>), &'static str> {
+    let index = match task_management::find_task(tasks, &arguments.task_id) {
+        Ok(index) => index,
+        Err(TaskManagementErrors::EmptyTasklist) => return Err("No tasks found!"),
+        Err(_) => return Err("Task doesn't exist!"),
+    };
+
+    let Some(on) = &arguments.on else {
+        return Ok((index, None));
+    };
+
+    match task_management::find_task(tasks, on) {
+        Ok(blocker) => Ok((index, Some(blocker))),
+        Err(_) => Err("The task to block on doesn't exist!"),
+    }
 }
 
-/// Converts a task_id to an index, preventing a runtime panic from attempting to subtract with
-/// overflow
+/// Finds the index of each task chosen by the given selectors, returned in order with duplicates
+/// removed
 ///
 /// Parameters
-/// task_id:   The task_id to convert to an index
-fn task_id_to_index(task_id: usize) -> usize {
-    let mut index = task_id;
+/// tasks:       The task vec the tasks belong to
+/// selectors:   The Task IDs, ranges and filters choosing the tasks
+fn task_ids_to_indexes(
+    tasks: &[Task],
+    selectors: &[TaskSelector],
+) -> Result<Vec<usize>, TaskManagementErrors> {
+    let mut indexes = Vec::new();
+
+    for selector in selectors {
+        indexes.extend(selector.select(tasks)?);
+    }
 
-    // Take one off of the index if not already zero (to prevent runtime panic) as Task ID's start
-    // at 1 not 0
-    index = index.saturating_sub(1);
+    sort_and_filter_task_ids(indexes, tasks.len())
+}
 
-    index
+/// Checks whether the given selectors can choose several tasks, which is anything but a single
+/// Task ID or last
+///
+/// Parameters
+/// selectors:   The Task IDs, ranges and filters choosing the tasks
+fn chooses_several(selectors: &[TaskSelector]) -> bool {
+    !matches!(selectors, [TaskSelector::Id(_) | TaskSelector::Last])
 }
 
 /// Manages the list command
@@ -582,6 +1823,30 @@ pub fn manage_config(config: &mut Config, arguments: ConfigCommand) -> String {
         return format!("Set smart_task_ids to {value}");
     }
 
+    // Checks if the user wants to change auto_complete_parents
+    if let Some(value) = arguments.auto_complete_parents {
+        config.set_auto_complete_parents(value);
+        return format!("Set auto_complete_parents to {value}");
+    }
+
+    // Checks if the user wants to change default_sort
+    if let Some(value) = arguments.default_sort {
+        config.set_default_sort(value.clone());
+        return format!("Set default_sort to {value}");
+    }
+
+    // Checks if the user wants to change wrap_width
+    if let Some(value) = arguments.wrap_width {
+        config.set_wrap_width(value);
+        return format!("Set wrap_width to {value}");
+    }
+
+    // Checks if the user wants to change pomodoro_minutes
+    if let Some(value) = arguments.pomodoro_minutes {
+        config.set_pomodoro_minutes(value);
+        return format!("Set pomodoro_minutes to {value}");
+    }
+
     // Checks if the user wants to change num_of_tasks
     if let Some(value) = arguments.num_of_tasks {
         config.set_num_of_tasks(value);
@@ -596,19 +1861,140 @@ pub fn manage_config(config: &mut Config, arguments: ConfigCommand) -> String {
     )
 }
 
-/// Sorts and removes duplicates in the given Vec<usize>
-fn sort_and_filter_task_ids(mut indexes: Vec<usize>) -> Vec<usize> {
+/// Exports the tasks in every list to the format given in the arguments. If an output file is given
+/// the export is written to it and None is returned, otherwise the export is returned to be printed
+///
+/// Parameters
+/// tasks:         The vec of tasks in the active list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// arguments:     The arguments for the command from the cli
+/// dry_run:       Whether to return the export instead of writing it to the output file
+pub fn export_tasks(
+    tasks: &[Task],
+    other_tasks: &[Task],
+    config: &Config,
+    arguments: ExportCommand,
+    dry_run: bool,
+) -> Result<Option<String>, &'static str> {
+    let export = match arguments.format {
+        FileFormat::Markdown => formats::markdown::export_tasks(tasks, other_tasks, config),
+        FileFormat::Taskwarrior => formats::taskwarrior::export_tasks(tasks, other_tasks),
+        FileFormat::Ics => formats::icalendar::export_tasks(tasks, other_tasks),
+    };
+
+    // A dry run shows what would have been written instead of writing it
+    match arguments.output.filter(|_| !dry_run) {
+        Some(path) => match fs::write(path, export) {
+            Ok(_) => Ok(None),
+            Err(_) => Err("Couldn't write the export!"),
+        },
+        None => Ok(Some(export)),
+    }
+}
+
+/// Imports tasks from the file given in the arguments, or from stdin, adding them to the list
+/// they belong to. The returned message reports anything that couldn't be imported
+///
+/// Parameters
+/// tasks:         The vec of tasks in the active list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// arguments:     The arguments for the command from the cli
+pub fn import_tasks(
+    tasks: &mut Vec<Task>,
+    other_tasks: &mut Vec<Task>,
+    config: &mut Config,
+    arguments: ImportCommand,
+) -> Result<String, &'static str> {
+    let mut input = String::new();
+
+    let read_result = match arguments.file {
+        Some(path) => fs::File::open(path).and_then(|mut file| file.read_to_string(&mut input)),
+        None => std::io::stdin().read_to_string(&mut input),
+    };
+
+    if read_result.is_err() {
+        return Err("Couldn't read the tasks to import!");
+    }
+
+    // Along with the tasks, some formats return notes on anything that couldn't be imported
+    let imported = match arguments.format {
+        FileFormat::Markdown => formats::markdown::import_tasks(&input, config)
+            .map(|imported_tasks| (imported_tasks, Vec::new())),
+        FileFormat::Taskwarrior => formats::taskwarrior::import_tasks(&input, config),
+        FileFormat::Ics => formats::icalendar::import_tasks(&input, config),
+    };
+
+    let (imported_tasks, notes) = match imported {
+        Ok(imported) => imported,
+        Err(err) => match err {
+            ImportErrors::NoTasksFound => return Err("No tasks found to import!"),
+            ImportErrors::InvalidFormat => return Err("The tasks aren't in the given format!"),
+            ImportErrors::TaskErrors(TaskErrors::EmptyDescription) => {
+                return Err("Tasks cannot have empty descriptions!")
+            }
+            ImportErrors::TaskErrors(TaskErrors::EmptyList) => {
+                return Err("A task must have a list!")
+            }
+        },
+    };
+
+    let mut message = format!("Imported {} tasks!", imported_tasks.len());
+
+    for note in notes {
+        write!(message, "\n    {}", note).unwrap();
+    }
+
+    // Tasks exported from this project keep their uid, so importing them again updates them
+    // instead of adding copies
+    let mut existing: Vec<&mut Task> = tasks.iter_mut().chain(other_tasks.iter_mut()).collect();
+
+    let new_tasks = task_management::update_matching_tasks(&mut existing, imported_tasks);
+
+    // Splitting the new tasks the same way filter_task_vec does
+    for task in new_tasks {
+        if task.list() == config.current_list() {
+            tasks.push(task);
+            continue;
+        }
+        other_tasks.push(task);
+    }
+
+    Ok(message)
+}
+
+/// Sorts and removes duplicates in the given Vec<usize>, checking that at least one task was chosen
+/// and that every index is in the tasks vec
+///
+/// Parameters
+/// indexes:     The indexes of the chosen tasks
+/// tasks_len:   The number of tasks in the tasks vec
+fn sort_and_filter_task_ids(
+    mut indexes: Vec<usize>,
+    tasks_len: usize,
+) -> Result<Vec<usize>, TaskManagementErrors> {
     // Sorting the indexes and removing duplicates
     indexes.sort();
     indexes.dedup();
-    indexes
+
+    if indexes.is_empty() {
+        return Err(TaskManagementErrors::NoTasksSelected);
+    }
+
+    if indexes.iter().any(|index| *index >= tasks_len) {
+        return Err(TaskManagementErrors::TaskDoesntExist);
+    }
+
+    Ok(indexes)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use crate::args::AddCommand;
+    use crate::config::StatusDefinition;
+    use crate::test_helpers::task;
 
     #[test]
     /// Tests if the new_task command works as expected
@@ -619,14 +2005,21 @@ mod tests {
 
         let list = config.current_list();
 
-        let expected_task = Task::new(description.clone(), TaskStatus::NotStarted, list).unwrap();
+        let mut expected_task =
+            Task::new(description.clone(), TaskStatus::NotStarted, list).unwrap();
 
         let arguments = AddCommand {
             description: Some(description),
+            parent: None,
+            repeat: None,
         };
 
         let genereated_task = new_task(arguments.description.unwrap(), &config).unwrap();
 
+        // The creation time is stamped when the task is made, so it can't be known beforehand
+        assert!(genereated_task.created().is_some());
+        expected_task.set_created(genereated_task.created());
+
         assert_eq!(expected_task, genereated_task)
     }
 
@@ -734,7 +2127,11 @@ mod tests {
         )
         .unwrap()];
 
-        cleanup_list(&mut tasks, &mut other_tasks, arguments);
+        let mut archive = Vec::new();
+
+        cleanup_list(&mut tasks, &mut other_tasks, &mut archive, arguments, true);
+
+        assert_eq!(archive.len(), 1);
 
         assert_eq!(
             tasks,
@@ -802,7 +2199,7 @@ mod tests {
             .unwrap(),
         ];
 
-        cleanup_list(&mut tasks, &mut other_tasks, arguments);
+        cleanup_list(&mut tasks, &mut other_tasks, &mut Vec::new(), arguments, true);
 
         assert_eq!(
             tasks,
@@ -861,38 +2258,19 @@ mod tests {
 
         // Declaring multiple task ids
         let arguments = DeleteCommand {
-            task_ids: vec![1, 2],
+            task_ids: vec![TaskId::from(1).into(), TaskId::from(2).into()],
         };
 
         // Asserting a plural tasks is returned when there are multiple tasks
-        assert_eq!(delete_task(&mut tasks.clone(), arguments), "Tasks deleted!");
+        assert_eq!(delete_task(&mut tasks.clone(), arguments, true), "Tasks deleted!");
 
         // Declaring a singular task id
-        let arguments = DeleteCommand { task_ids: vec![1] };
+        let arguments = DeleteCommand {
+            task_ids: vec![TaskId::from(1).into()],
+        };
 
         // Asserting a plural tasks is returned when there are multiple tasks
-        assert_eq!(delete_task(&mut tasks.clone(), arguments), "Task deleted!");
-    }
-
-    #[test]
-    /// Tests if the task_id_to_index function works
-    fn task_id_to_index_works() {
-        let task_id: usize = 1;
-
-        let index = task_id_to_index(task_id);
-
-        assert_eq!(index, 0)
-    }
-
-    #[test]
-    /// Tests if the task_id_to_index function can handle task_id being 0 and not create a panic
-    /// from attempting to subtract with overflow
-    fn task_id_to_index_handles_zero_as_input() {
-        let task_id: usize = 0;
-
-        let index = task_id_to_index(task_id);
-
-        assert_eq!(index, 0)
+        assert_eq!(delete_task(&mut tasks.clone(), arguments, true), "Task deleted!");
     }
 
     #[test]
@@ -914,15 +2292,57 @@ mod tests {
         ];
 
         let arguments = StartCommand {
-            task_ids: vec![1, 2],
+            task_ids: vec![TaskId::from(1).into(), TaskId::from(2).into()],
+            force: false,
         };
 
-        start_task(&mut tasks_vec, arguments);
+        start_task(&mut tasks_vec, &mut Vec::new(), arguments);
 
         assert_eq!(tasks_vec[0].status(), TaskStatus::InProgress);
         assert_eq!(tasks_vec[1].status(), TaskStatus::InProgress);
     }
 
+    #[test]
+    /// Tests if starting a task times it, and starting it again once its timer is stopped starts
+    /// a new session instead of failing
+    fn start_task_resumes_timer() {
+        let mut task = Task::new(
+            String::from("A basic task"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        task.set_uid(String::from("1"));
+
+        let mut tasks_vec = vec![task];
+
+        let mut sessions = Vec::new();
+
+        let start = || StartCommand {
+            task_ids: vec![TaskId::from(1).into()],
+            force: false,
+        };
+
+        start_task(&mut tasks_vec, &mut sessions, start());
+
+        assert_eq!(
+            start_task(&mut tasks_vec, &mut sessions, start()),
+            "Task is already in progress!"
+        );
+
+        let arguments = StopCommand {
+            task_ids: Vec::new(),
+        };
+
+        assert_eq!(stop_task(&tasks_vec, &mut sessions, arguments), "Timer stopped!");
+
+        start_task(&mut tasks_vec, &mut sessions, start());
+
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions[1].end.is_none());
+    }
+
     #[test]
     /// Tests if the start command works with multiple ids
     fn delete_task_with_multiple_task_ids_works() {
@@ -942,10 +2362,10 @@ mod tests {
         ];
 
         let arguments = DeleteCommand {
-            task_ids: vec![1, 2],
+            task_ids: vec![TaskId::from(1).into(), TaskId::from(2).into()],
         };
 
-        delete_task(&mut tasks_vec, arguments);
+        delete_task(&mut tasks_vec, arguments, true);
 
         assert_eq!(tasks_vec.len(), 0);
     }
@@ -969,7 +2389,8 @@ mod tests {
         ];
 
         let arguments = FinishCommand {
-            task_ids: vec![1, 2],
+            task_ids: vec![TaskId::from(1).into(), TaskId::from(2).into()],
+            force: false,
         };
 
         finish_task(&mut tasks_vec, arguments);
@@ -997,7 +2418,7 @@ mod tests {
         ];
 
         let arguments = RestartCommand {
-            task_ids: vec![1, 2],
+            task_ids: vec![TaskId::from(1).into(), TaskId::from(2).into()],
         };
 
         restart_task(&mut tasks_vec, arguments);
@@ -1006,12 +2427,169 @@ mod tests {
         assert_eq!(tasks_vec[1].status(), TaskStatus::NotStarted);
     }
 
+    #[test]
+    fn finish_task_checks_every_task_before_changing_any() {
+        let mut tasks_vec = vec![
+            task("First task", TaskStatus::NotStarted, "Main"),
+            task("Blocked task", TaskStatus::Blocked, "Main"),
+        ];
+
+        let arguments = FinishCommand {
+            task_ids: vec![TaskId::from(1).into(), TaskId::from(2).into()],
+            force: false,
+        };
+
+        assert_eq!(
+            finish_task(&mut tasks_vec, arguments),
+            "Task is blocked, use --force to finish it anyway"
+        );
+        assert_eq!(tasks_vec[0].status(), TaskStatus::NotStarted);
+    }
+
+    #[test]
+    fn finish_task_with_all_skips_finished_tasks() {
+        let mut tasks_vec = vec![
+            task("Completed task", TaskStatus::Completed, "Main"),
+            task("Cancelled task", TaskStatus::Cancelled, "Main"),
+            task("Open task", TaskStatus::NotStarted, "Main"),
+        ];
+
+        let arguments = FinishCommand {
+            task_ids: vec![TaskSelector::All],
+            force: false,
+        };
+
+        assert_eq!(finish_task(&mut tasks_vec, arguments), "Task has been completed!");
+        assert_eq!(tasks_vec[1].status(), TaskStatus::Cancelled);
+        assert_eq!(tasks_vec[2].status(), TaskStatus::Completed);
+    }
+
+    #[test]
+    fn start_task_with_range_skips_finished_tasks() {
+        let mut tasks_vec = vec![
+            task("Completed task", TaskStatus::Completed, "Main"),
+            task("Open task", TaskStatus::NotStarted, "Main"),
+        ];
+
+        let arguments = StartCommand {
+            task_ids: vec![TaskSelector::Range(TaskId::from(1), TaskId::from(2))],
+            force: false,
+        };
+
+        start_task(&mut tasks_vec, &mut Vec::new(), arguments);
+
+        assert_eq!(tasks_vec[0].status(), TaskStatus::Completed);
+        assert_eq!(tasks_vec[1].status(), TaskStatus::InProgress);
+    }
+
+    #[test]
+    fn cancel_task_with_all_skips_cancelled_tasks() {
+        let mut tasks_vec = vec![
+            task("Cancelled task", TaskStatus::Cancelled, "Main"),
+            task("Open task", TaskStatus::NotStarted, "Main"),
+        ];
+
+        let arguments = CancelCommand {
+            task_ids: vec![TaskSelector::All],
+        };
+
+        assert_eq!(cancel_task(&mut tasks_vec, arguments), "Task has been cancelled!");
+        assert_eq!(tasks_vec[1].status(), TaskStatus::Cancelled);
+    }
+
     #[test]
     fn sort_and_filter_task_ids_works() {
         let indexes = vec![1, 2, 2, 5, 1];
 
-        let indexes = sort_and_filter_task_ids(indexes);
+        let indexes = sort_and_filter_task_ids(indexes, 6);
+
+        assert_eq!(indexes, Ok(vec![1, 2, 5]));
+        assert_eq!(
+            sort_and_filter_task_ids(vec![1, 6], 6),
+            Err(TaskManagementErrors::TaskDoesntExist)
+        );
+        assert_eq!(
+            sort_and_filter_task_ids(Vec::new(), 6),
+            Err(TaskManagementErrors::NoTasksSelected)
+        );
+    }
+
+    #[test]
+    /// Tests if cleanup_list removes cancelled tasks as well as completed ones
+    fn cleanup_list_removes_cancelled_tasks() {
+        let arguments = CleanupCommand { all: false };
+
+        let mut tasks = vec![
+            Task::new(
+                String::from("A cancelled task"),
+                TaskStatus::Cancelled,
+                String::from("Main"),
+            )
+            .unwrap(),
+            Task::new(
+                String::from("A blocked task"),
+                TaskStatus::Blocked,
+                String::from("Main"),
+            )
+            .unwrap(),
+        ];
+
+        cleanup_list(&mut tasks, &mut Vec::new(), &mut Vec::new(), arguments, true);
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].status(), TaskStatus::Blocked);
+    }
+
+    #[test]
+    /// Tests if set_task_status uses the statuses in the config's workflow, recording when tasks
+    /// were given a status that counts as done
+    fn set_task_status_uses_the_workflow() {
+        let mut config = Config::new();
+
+        config.set_statuses(vec![
+            StatusDefinition {
+                name: String::from("Review"),
+                glyph: String::from("R"),
+                color: String::from("blue"),
+                done: false,
+                active: false,
+            },
+            StatusDefinition {
+                name: String::from("Done"),
+                glyph: String::from("D"),
+                color: String::from("green"),
+                done: true,
+                active: false,
+            },
+        ]);
+
+        let mut tasks = vec![Task::new(
+            String::from("A basic task"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap()];
+
+        let arguments = |status: &str| SetStatusCommand {
+            task_id: TaskId::from(1),
+            status: String::from(status),
+            force: false,
+        };
+
+        assert_eq!(
+            set_task_status(&mut tasks, &mut Vec::new(), &config, arguments("review")),
+            "Status updated!"
+        );
+        assert_eq!(tasks[0].status(), TaskStatus::Custom(String::from("Review")));
+        assert!(!tasks[0].is_finished());
 
-        assert_eq!(indexes, vec![1, 2, 5]);
+        set_task_status(&mut tasks, &mut Vec::new(), &config, arguments("done"));
+
+        assert!(tasks[0].is_finished());
+
+        assert_eq!(
+            set_task_status(&mut tasks, &mut Vec::new(), &config, arguments("shipped")),
+            "That status doesn't exist, see the statuses with todo config"
+        );
     }
 }
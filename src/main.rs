@@ -5,6 +5,8 @@ use clap::Parser;
 use todo::args;
 use todo::args::TodoArgs;
 
+use todo::dry_run::Snapshot;
+
 use todo::task::Task;
 
 use todo::print_info;
@@ -12,7 +14,7 @@ use todo::print_info;
 fn main() {
     let args = TodoArgs::parse();
 
-    let unfiltered_tasks_vec: Vec<Task> = match todo::read_tasks_file() {
+    let mut unfiltered_tasks_vec: Vec<Task> = match todo::read_tasks_file(args.dry_run) {
         Ok(tasks_vec) => tasks_vec,
         Err(err) => {
             print_info(err);
@@ -20,8 +22,45 @@ fn main() {
         }
     };
 
+    // Tasks from older tasks files won't have a unique id yet
+    todo::assign_task_uids(&mut unfiltered_tasks_vec);
+
+    let mut archive: Vec<Task> = match todo::read_archive_file() {
+        Ok(archive) => archive,
+        Err(err) => {
+            print_info(err);
+            process::exit(1);
+        }
+    };
+
+    let mut sessions = match todo::read_time_file() {
+        Ok(sessions) => sessions,
+        Err(err) => {
+            print_info(err);
+            process::exit(1);
+        }
+    };
+
     let mut config = todo::read_config_file();
 
+    // Keeping the state from before the command so a dry run can show what it would change
+    let tasks_before = match args.dry_run {
+        true => unfiltered_tasks_vec.clone(),
+        false => Vec::new(),
+    };
+
+    let archive_before = archive.clone();
+
+    let sessions_before = sessions.clone();
+
+    let config_before = config.clone();
+
+    // A dry run doesn't ask, as it only shows what would happen
+    let assume_yes = args.yes || args.dry_run;
+
+    // A dry run describes what would change instead of saying the command worked
+    let command_feedback = config.command_feedback() && !args.dry_run;
+
     // Filtering the tasks vec so that only the current list is worked on
     let filtered_vecs = todo::filter_task_vec(unfiltered_tasks_vec, &config);
 
@@ -31,20 +70,77 @@ fn main() {
 
     match args.command {
         args::Commands::Tasks(arguments) => {
-            match todo::list_tasks(&mut tasks_vec, &other_tasks, &config, arguments) {
+            match todo::list_tasks(&mut tasks_vec, &mut other_tasks, &config, arguments) {
                 // List is already formated so print it as normal
                 Ok(list) => println!("{}", &list),
                 Err(err) => print_info(err),
             }
         }
 
+        args::Commands::Board(arguments) => {
+            match todo::show_board(&tasks_vec, &other_tasks, &config, arguments) {
+                Ok(board) => print!("{}", board),
+                Err(err) => print_info(err),
+            }
+        }
+
+        args::Commands::Today(arguments) => {
+            match arguments.reset {
+                true => {
+                    let message = todo::reset_today(&mut tasks_vec, &mut other_tasks);
+                    if command_feedback {
+                        print_info(message);
+                    }
+                }
+                false => match todo::show_today(&tasks_vec, &other_tasks, &config) {
+                    Ok(agenda) => print!("{}", agenda),
+                    Err(err) => print_info(err),
+                },
+            }
+        }
+
+        args::Commands::Search(arguments) => {
+            match todo::search_tasks(&tasks_vec, &other_tasks, &config, arguments) {
+                Ok(hits) => print!("{}", hits),
+                Err(err) => print_info(err),
+            }
+        }
+
         args::Commands::Cleanup(arguments) => {
-            if config.command_feedback() {
-                print_info(todo::cleanup_list(
-                    &mut tasks_vec,
-                    &mut other_tasks,
-                    arguments,
-                ))
+            let message = todo::cleanup_list(
+                &mut tasks_vec,
+                &mut other_tasks,
+                &mut archive,
+                arguments,
+                assume_yes,
+            );
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Stats(arguments) => {
+            let stats = todo::show_stats(&tasks_vec, &other_tasks, &archive, &config, arguments);
+            println!("{}", stats.trim_end());
+        }
+
+        args::Commands::Archive(arguments) => {
+            match todo::show_archive(&archive, &config, arguments) {
+                Ok(archived) => print!("{}", archived),
+                Err(err) => print_info(err),
+            }
+        }
+
+        args::Commands::Unarchive(arguments) => {
+            let message = todo::unarchive_task(
+                &mut tasks_vec,
+                &mut other_tasks,
+                &mut archive,
+                &config,
+                arguments,
+            );
+            if command_feedback {
+                print_info(message);
             }
         }
 
@@ -52,52 +148,182 @@ fn main() {
             // If the user provides a description, add the task with that description, otherwise
             // enter add mode
             match arguments.description {
-                Some(description) => {
-                    match todo::new_task(description, &config) {
-                        Ok(task) => {
-                            tasks_vec.push(task);
-                            if config.command_feedback() {
-                                print_info("Task added!")
+                Some(_) => {
+                    match todo::add_task(&mut tasks_vec, arguments, &config) {
+                        Ok(message) => {
+                            if command_feedback {
+                                print_info(message)
                             }
                         }
                         Err(err) => print_info(err),
                     };
                 },
-                None => todo::add_mode(&mut tasks_vec, &config),
+                None => todo::add_mode(&mut tasks_vec, &mut other_tasks, &config, args.dry_run),
             }
         }
 
         args::Commands::Delete(arguments) => {
-            let message = todo::delete_task(&mut tasks_vec, arguments);
-            if config.command_feedback() {
+            let message = todo::delete_task(&mut tasks_vec, arguments, assume_yes);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Mv(arguments) => {
+            let message = todo::move_task(&mut tasks_vec, arguments);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Top(arguments) => {
+            let message = todo::move_task_to_top(&mut tasks_vec, arguments);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Bottom(arguments) => {
+            let message = todo::move_task_to_bottom(&mut tasks_vec, arguments);
+            if command_feedback {
                 print_info(message);
             }
         }
 
         args::Commands::Start(arguments) => {
-            let message = todo::start_task(&mut tasks_vec, arguments);
-            if config.command_feedback() {
+            let message = todo::start_task(&mut tasks_vec, &mut sessions, arguments);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Stop(arguments) => {
+            let message = todo::stop_task(&tasks_vec, &mut sessions, arguments);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Time(arguments) => {
+            match todo::time_report(
+                &tasks_vec,
+                &other_tasks,
+                &archive,
+                &sessions,
+                arguments,
+                args.dry_run,
+            ) {
+                Ok(Some(report)) => print!("{}", report),
+                Ok(None) => {
+                    if command_feedback {
+                        print_info("Report written!");
+                    }
+                }
+                Err(err) => print_info(err),
+            }
+        }
+
+        args::Commands::Focus(_) if args.dry_run => {
+            // A pomodoro can't be tried out without sitting through it, so nothing is done
+            print_info("Nothing was written, the task would be focused on for a pomodoro");
+            process::exit(0);
+        }
+
+        args::Commands::Focus(arguments) => {
+            let message = todo::focus_task(&mut tasks_vec, &mut sessions, &config, arguments);
+            todo::auto_complete_parents(&mut tasks_vec, &config);
+            if command_feedback {
                 print_info(message);
             }
         }
 
         args::Commands::Finish(arguments) => {
             let message = todo::finish_task(&mut tasks_vec, arguments);
-            if config.command_feedback() {
+            todo::auto_complete_parents(&mut tasks_vec, &config);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Show(arguments) => match todo::show_task(&tasks_vec, arguments) {
+            Ok(details) => println!("{}", details.trim_end()),
+            Err(err) => print_info(err),
+        },
+
+        args::Commands::Note(arguments) => {
+            match todo::note_task(&mut tasks_vec, arguments) {
+                Ok(Some(notes)) => println!("{}", notes),
+                Ok(None) => {
+                    if command_feedback {
+                        print_info("Notes updated!");
+                    }
+                }
+                Err(err) => print_info(err),
+            }
+        }
+
+        args::Commands::Repeat(arguments) => {
+            let message = todo::repeat_task(&mut tasks_vec, arguments);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Plan(arguments) => {
+            let message = todo::plan_task(&mut tasks_vec, arguments);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Cancel(arguments) => {
+            let message = todo::cancel_task(&mut tasks_vec, arguments);
+            todo::auto_complete_parents(&mut tasks_vec, &config);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::SetStatus(arguments) => {
+            let message = todo::set_task_status(&mut tasks_vec, &mut sessions, &config, arguments);
+            todo::auto_complete_parents(&mut tasks_vec, &config);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Block(arguments) => {
+            let message = todo::block_task(&mut tasks_vec, arguments);
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Unblock(arguments) => {
+            let message = todo::unblock_task(&mut tasks_vec, arguments);
+            if command_feedback {
                 print_info(message);
             }
         }
 
         args::Commands::Restart(arguments) => {
             let message = todo::restart_task(&mut tasks_vec, arguments);
-            if config.command_feedback() {
+            if command_feedback {
                 print_info(message);
             }
         }
 
         args::Commands::Update(arguments) => {
             let message = todo::update_task(&mut tasks_vec, arguments);
-            if config.command_feedback() {
+            if command_feedback {
+                print_info(message);
+            }
+        }
+
+        args::Commands::Edit(arguments) => {
+            let message = todo::edit_task(&mut tasks_vec, &mut other_tasks, &config, arguments);
+            todo::auto_complete_parents(&mut tasks_vec, &config);
+            if command_feedback {
                 print_info(message);
             }
         }
@@ -113,10 +339,57 @@ fn main() {
             println!("{}", todo::manage_config(&mut config, arguments));
         }
 
+        args::Commands::Export(arguments) => {
+            match todo::export_tasks(&tasks_vec, &other_tasks, &config, arguments, args.dry_run) {
+                // The export wasn't written to a file so print it as is
+                Ok(Some(export)) => print!("{}", export),
+                Ok(None) => {
+                    if command_feedback {
+                        print_info("Tasks exported!");
+                    }
+                }
+                Err(err) => print_info(err),
+            }
+        }
+
+        args::Commands::Import(arguments) => {
+            match todo::import_tasks(&mut tasks_vec, &mut other_tasks, &mut config, arguments) {
+                Ok(message) => {
+                    if command_feedback {
+                        print_info(&message);
+                    }
+                }
+                Err(err) => print_info(err),
+            }
+
+            // Imported tasks can go into any list, so every list is checked
+            todo::auto_complete_parents(&mut tasks_vec, &config);
+            todo::auto_complete_parents(&mut other_tasks, &config);
+        }
+
+        args::Commands::Ui => {
+            let result = todo::run_ui(
+                &mut tasks_vec,
+                &mut other_tasks,
+                &mut sessions,
+                &mut config,
+                assume_yes,
+            );
+
+            if let Err(err) = result {
+                print_info(err);
+            }
+        }
+
         args::Commands::Nuke => {
+            if args.dry_run {
+                print_info("Nothing was written, the .todo dir would be nuked");
+                process::exit(0);
+            }
+
             // This will be printed regardless of the config option as the user should always know
             // that this command worked
-            print_info(todo::nuke_todo());
+            print_info(todo::nuke_todo(assume_yes));
             // Exiting the program so that it doesn't attempt to serialise the program state
             process::exit(1);
         }
@@ -128,11 +401,50 @@ fn main() {
     // Ensuring all tasks belong to a valid list
     tasks_vec.retain(|task| config.is_valid_list(&task.list()));
 
+    // Giving any tasks created by the command a unique id
+    todo::assign_task_uids(&mut tasks_vec);
+
+    // Timers only run while their task is in progress or another active status
+    todo::stop_idle_timers(&mut sessions, &tasks_vec, &config);
+
+    if args.dry_run {
+        let before = Snapshot {
+            tasks: &tasks_before,
+            archive: &archive_before,
+            sessions: &sessions_before,
+            config: &config_before,
+        };
+
+        let after = Snapshot {
+            tasks: &tasks_vec,
+            archive: &archive,
+            sessions: &sessions,
+            config: &config,
+        };
+
+        println!("{}", todo::describe_dry_run(&before, &after));
+        return;
+    }
+
     // Writing to the tasks file
     if let Err(err) = todo::write_tasks_file(tasks_vec) {
         print_info(err);
     }
 
+    // Only writing the time file when it changed, so it isn't created until a task is timed
+    if sessions != sessions_before {
+        if let Err(err) = todo::write_time_file(sessions) {
+            print_info(err);
+        }
+    }
+
+    // Only writing the archive when it changed, so it isn't created until something is archived
+    if archive != archive_before {
+        if let Err(err) = todo::write_archive_file(archive) {
+            print_info(err);
+        }
+    }
+
     // Writing to the config file
     if let Err(err) = todo::write_config_file(config) {
         print_info(err);
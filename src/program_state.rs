@@ -2,15 +2,29 @@ use crate::task::Task;
 
 use crate::config::Config;
 
+use crate::time_tracking::TimeSession;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
 use std::fs::{self, File};
 
+use std::io::{Read, Write};
+
 /// Const for storing the file name to write the tasks to
 const TASK_FILE_NAME: &str = ".todo/tasks.csv";
 
+/// Const for storing the file name to write the archived tasks to
+const ARCHIVE_FILE_NAME: &str = ".todo/archive.csv";
+
+/// Const for storing the file name to write the time tracking sessions to
+const TIME_FILE_NAME: &str = ".todo/time.csv";
+
 /// Const for storing the file name to write the config to
 const CONFIG_FILE_NAME: &str = ".todo/config.yaml";
 
 /// Enum for storing possible serialization errros
+#[derive(Debug, PartialEq, Eq)]
 pub enum SerializationErrors {
     UnableToCreateWriter,
     FailedToSerialize,
@@ -22,13 +36,38 @@ pub enum SerializationErrors {
 /// Parameters
 /// tasks:   The tasks list to write to the file
 pub fn serialize_tasks(tasks: Vec<Task>) -> Result<(), SerializationErrors> {
-    let mut writer = match csv::Writer::from_path(TASK_FILE_NAME) {
+    let writer = match csv::Writer::from_path(TASK_FILE_NAME) {
         Ok(writer) => writer,
         Err(_) => return Err(SerializationErrors::UnableToCreateWriter),
     };
 
-    for task in tasks {
-        match writer.serialize(task) {
+    write_rows(tasks, writer)
+}
+
+/// Seralializes the archived tasks to the archive file
+///
+/// Parameters
+/// archive:   The archived tasks to write to the file
+pub fn serialize_archive(archive: Vec<Task>) -> Result<(), SerializationErrors> {
+    let writer = match csv::Writer::from_path(ARCHIVE_FILE_NAME) {
+        Ok(writer) => writer,
+        Err(_) => return Err(SerializationErrors::UnableToCreateWriter),
+    };
+
+    write_rows(archive, writer)
+}
+
+/// Writes the given rows, such as tasks, with the given csv writer
+///
+/// Parameters
+/// rows:     The rows to write
+/// writer:   The csv writer to write them with
+fn write_rows<T: Serialize, W: Write>(
+    rows: Vec<T>,
+    mut writer: csv::Writer<W>,
+) -> Result<(), SerializationErrors> {
+    for row in rows {
+        match writer.serialize(row) {
             Ok(_) => (),
             Err(_) => return Err(SerializationErrors::FailedToSerialize),
         };
@@ -57,6 +96,7 @@ pub fn serialize_config(config: Config) -> Result<(), SerializationErrors> {
 } 
 
 /// Enum for storing possible deserialization errros
+#[derive(Debug, PartialEq, Eq)]
 pub enum DeserializationErrors {
     FailedToCreateReader,
     FailedToDeserializeTask,
@@ -64,23 +104,68 @@ pub enum DeserializationErrors {
 
 /// Deserializes the serializes data in the tasks file to a Vec<Task>
 pub fn deserialize_tasks() -> Result<Vec<Task>, DeserializationErrors> {
-    let mut tasks: Vec<Task> = Vec::new();
-
-    let mut reader = match csv::Reader::from_path(TASK_FILE_NAME) {
+    let reader = match csv::Reader::from_path(TASK_FILE_NAME) {
         Ok(writer) => writer,
         Err(_) => return Err(DeserializationErrors::FailedToCreateReader),
     };
 
+    read_rows(reader)
+}
+
+/// Deserializes the archived tasks in the archive file, which is empty if nothing has been
+/// archived yet
+pub fn deserialize_archive() -> Result<Vec<Task>, DeserializationErrors> {
+    let file = match File::open(ARCHIVE_FILE_NAME) {
+        Ok(file) => file,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    read_rows(csv::Reader::from_reader(file))
+}
+
+/// Seralializes the time tracking sessions to the time file
+///
+/// Parameters
+/// sessions:   The sessions to write to the file
+pub fn serialize_sessions(sessions: Vec<TimeSession>) -> Result<(), SerializationErrors> {
+    let writer = match csv::Writer::from_path(TIME_FILE_NAME) {
+        Ok(writer) => writer,
+        Err(_) => return Err(SerializationErrors::UnableToCreateWriter),
+    };
+
+    write_rows(sessions, writer)
+}
+
+/// Deserializes the time tracking sessions in the time file, which is empty if no time has been
+/// tracked yet
+pub fn deserialize_sessions() -> Result<Vec<TimeSession>, DeserializationErrors> {
+    let file = match File::open(TIME_FILE_NAME) {
+        Ok(file) => file,
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    read_rows(csv::Reader::from_reader(file))
+}
+
+/// Reads every row, such as a task, out of the given csv reader
+///
+/// Parameters
+/// reader:   The csv reader to read the rows from
+fn read_rows<T: DeserializeOwned, R: Read>(
+    mut reader: csv::Reader<R>,
+) -> Result<Vec<T>, DeserializationErrors> {
+    let mut rows: Vec<T> = Vec::new();
+
     for result in reader.deserialize() {
-        let task: Task = match result {
-            Ok(task) => task,
+        let row: T = match result {
+            Ok(row) => row,
             Err(_) => return Err(DeserializationErrors::FailedToDeserializeTask),
         };
 
-        tasks.push(task)
+        rows.push(row)
     }
 
-    Ok(tasks)
+    Ok(rows)
 }
 
 /// Deserializes the yaml data in the config file to a Config
@@ -96,4 +181,82 @@ pub fn deserialize_config() -> Result<Config, DeserializationErrors> {
     };
 
     Ok(config)
-} 
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::task::{Priority, TaskStatus};
+
+    use chrono::{NaiveDate, Utc};
+
+    #[test]
+    /// Tests if tasks files written before the extra task fields were added can still be read
+    fn read_tasks_reads_old_tasks_files() {
+        let csv = "description,status,list\nA basic task,InProgress,Main\n";
+
+        let tasks: Vec<Task> = read_rows(csv::Reader::from_reader(csv.as_bytes())).unwrap();
+
+        assert_eq!(
+            tasks,
+            vec![Task::new(
+                String::from("A basic task"),
+                TaskStatus::InProgress,
+                String::from("Main")
+            )
+            .unwrap()]
+        )
+    }
+
+    #[test]
+    /// Tests if tasks written by write_rows are read back the same by read_rows
+    fn write_tasks_can_be_read_back() {
+        let mut task = Task::new(
+            String::from("A basic task"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        task.set_created(Some(Utc::now()));
+        task.set_due(NaiveDate::from_ymd_opt(2023, 1, 5));
+        task.set_priority(Some(Priority::High));
+        task.set_tags(vec![String::from("docs"), String::from("cli")]);
+
+        let mut csv = Vec::new();
+
+        write_rows(vec![task.clone()], csv::Writer::from_writer(&mut csv)).unwrap();
+
+        let tasks: Vec<Task> = read_rows(csv::Reader::from_reader(csv.as_slice())).unwrap();
+
+        assert_eq!(tasks, vec![task])
+    }
+
+    #[test]
+    /// Tests if notes with newlines, commas and quotes are escaped so the tasks file stays valid
+    fn write_tasks_escapes_multi_line_notes() {
+        let mut task = Task::new(
+            String::from("A basic task"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap();
+
+        task.set_notes(Some(String::from(
+            "First line, with a comma\n\"Quoted\" second line\r\n\nLast line",
+        )));
+
+        let mut csv = Vec::new();
+
+        write_rows(
+            vec![task.clone(), task.clone()],
+            csv::Writer::from_writer(&mut csv),
+        )
+        .unwrap();
+
+        let tasks: Vec<Task> = read_rows(csv::Reader::from_reader(csv.as_slice())).unwrap();
+
+        assert_eq!(tasks, vec![task.clone(), task])
+    }
+}
@@ -0,0 +1,382 @@
+use crate::task::{Task, TaskId};
+
+use crate::config::Config;
+
+use crate::task_management;
+
+use colored::Colorize;
+
+use std::cmp::Reverse;
+use std::fmt::Write;
+
+/// Struct for storing how well a query matched some text
+#[derive(Debug, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    /// Higher scores are better matches
+    pub score: i64,
+    /// The positions of the matched characters in the text, counted in characters
+    pub positions: Vec<usize>,
+}
+
+/// The part of a task a query matched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchedField {
+    Description,
+    Tags,
+    Notes,
+}
+
+/// Struct for storing a task that matched a search
+#[derive(Debug)]
+pub struct SearchHit<'a> {
+    pub list: String,
+    /// The task's ID in its own list
+    pub task_id: TaskId,
+    pub task: &'a Task,
+    pub field: MatchedField,
+    /// The text of the matched field, which for notes is only the matching line
+    pub text: String,
+    pub fuzzy_match: FuzzyMatch,
+}
+
+/// Matches every word of the query against the text, ignoring case. Each word scores highest when
+/// it appears in the text as is, and otherwise matches if its characters appear in order, with
+/// consecutive characters and characters at the start of words scoring higher
+///
+/// Parameters
+/// query:   The words to search for
+/// text:    The text to search in
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut positions: Vec<usize> = Vec::new();
+
+    for word in query.split_whitespace() {
+        let word: Vec<char> = word.to_lowercase().chars().collect();
+
+        let word_match = match find_word(&word, &text) {
+            Some(word_match) => word_match,
+            None => fuzzy_match_word(&word, &text)?,
+        };
+
+        score += word_match.score;
+        positions.extend(word_match.positions);
+    }
+
+    if positions.is_empty() {
+        return None;
+    }
+
+    positions.sort_unstable();
+    positions.dedup();
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Returns whether the character at the given position starts a word
+///
+/// Parameters
+/// text:       The text the character is in
+/// position:   The position of the character
+fn starts_word(text: &[char], position: usize) -> bool {
+    position == 0 || !text[position - 1].is_alphanumeric()
+}
+
+/// Finds the word in the text as is, preferring an occurrence at the start of a word
+///
+/// Parameters
+/// word:   The lowercase word to find
+/// text:   The lowercase text to search in
+fn find_word(word: &[char], text: &[char]) -> Option<FuzzyMatch> {
+    if word.is_empty() || word.len() > text.len() {
+        return None;
+    }
+
+    let starts: Vec<usize> = (0..=text.len() - word.len())
+        .filter(|&start| &text[start..start + word.len()] == word)
+        .collect();
+
+    let start = starts
+        .iter()
+        .copied()
+        .find(|&start| starts_word(text, start))
+        .or_else(|| starts.first().copied())?;
+
+    let mut score = 100 + 10 * word.len() as i64;
+
+    if starts_word(text, start) {
+        score += 20;
+    }
+
+    Some(FuzzyMatch {
+        score,
+        positions: (start..start + word.len()).collect(),
+    })
+}
+
+/// Matches the characters of the word in order anywhere in the text, trying each place the word
+/// could start and keeping the best. Matches spread so thinly that they score nothing are ignored
+///
+/// Parameters
+/// word:   The lowercase word to match
+/// text:   The lowercase text to search in
+fn fuzzy_match_word(word: &[char], text: &[char]) -> Option<FuzzyMatch> {
+    let first = word.first()?;
+
+    (0..text.len())
+        .filter(|&start| &text[start] == first)
+        .filter_map(|start| fuzzy_match_from(word, text, start))
+        .filter(|word_match| word_match.score > 0)
+        .fold(None, |best: Option<FuzzyMatch>, word_match| match best {
+            Some(best) if best.score >= word_match.score => Some(best),
+            _ => Some(word_match),
+        })
+}
+
+/// Matches the characters of the word in order, taking the first occurrence of each character
+/// from the given start
+///
+/// Parameters
+/// word:    The lowercase word to match
+/// text:    The lowercase text to search in
+/// start:   The position to start matching from
+fn fuzzy_match_from(word: &[char], text: &[char], start: usize) -> Option<FuzzyMatch> {
+    let mut score = 0;
+    let mut positions: Vec<usize> = Vec::new();
+
+    let mut position = start;
+
+    for character in word {
+        let found = (position..text.len()).find(|&other| &text[other] == character)?;
+
+        score += match positions.last() {
+            Some(&last) if last + 1 == found => 8,
+            _ if starts_word(text, found) => 5,
+            // Gaps between the matched characters make for a worse match
+            Some(&last) => 1 - (found - last - 1).min(5) as i64,
+            None => 1,
+        };
+
+        positions.push(found);
+        position = found + 1;
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// Finds the tasks in every list that match the query in their description, tags or notes,
+/// ranked with the best matches first. Matches in the description rank above the same match in
+/// the tags, which rank above the notes
+///
+/// Parameters
+/// tasks:         The vec of tasks in the active list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// query:         The text to search for
+pub fn search<'a>(
+    tasks: &'a [Task],
+    other_tasks: &'a [Task],
+    config: &Config,
+    query: &str,
+) -> Vec<SearchHit<'a>> {
+    let mut hits: Vec<SearchHit> = Vec::new();
+
+    for list in config.lists_iter() {
+        // Task IDs are given within each list, in the order the list's tasks are stored
+        let list_tasks: Vec<&Task> = match list == &config.current_list() {
+            true => tasks.iter().collect(),
+            false => other_tasks
+                .iter()
+                .filter(|task| &task.list() == list)
+                .collect(),
+        };
+
+        let owned: Vec<Task> = list_tasks.iter().map(|task| (*task).clone()).collect();
+
+        for (task_id, index) in task_management::task_tree(&owned) {
+            if let Some(hit) = match_task(list_tasks[index], list, task_id, query) {
+                hits.push(hit);
+            }
+        }
+    }
+
+    // Sorting is stable, so equal matches stay in list order
+    hits.sort_by_key(|hit| Reverse(hit.fuzzy_match.score));
+
+    hits
+}
+
+/// Matches the query against the description, tags and notes of a task, returning the best match
+///
+/// Parameters
+/// task:      The task to match
+/// list:      The list the task is in
+/// task_id:   The task's ID in its list
+/// query:     The text to search for
+fn match_task<'a>(
+    task: &'a Task,
+    list: &str,
+    task_id: TaskId,
+    query: &str,
+) -> Option<SearchHit<'a>> {
+    let mut fields: Vec<(MatchedField, String)> = vec![
+        (MatchedField::Description, task.description()),
+        (MatchedField::Tags, task.tags().join(" ")),
+    ];
+
+    if let Some(notes) = task.notes() {
+        fields.extend(
+            notes
+                .lines()
+                .map(|line| (MatchedField::Notes, line.trim().to_owned())),
+        );
+    }
+
+    fields
+        .into_iter()
+        .filter_map(|(field, text)| {
+            let mut fuzzy_match = fuzzy_match(query, &text)?;
+
+            fuzzy_match.score -= match field {
+                MatchedField::Description => 0,
+                MatchedField::Tags => 5,
+                MatchedField::Notes => 10,
+            };
+
+            Some((field, text, fuzzy_match))
+        })
+        // Taking the first of the best matches, so the description wins ties
+        .fold(
+            None,
+            |best: Option<(MatchedField, String, FuzzyMatch)>, hit| match best {
+                Some(best) if best.2.score >= hit.2.score => Some(best),
+                _ => Some(hit),
+            },
+        )
+        .map(|(field, text, fuzzy_match)| SearchHit {
+            list: list.to_owned(),
+            task_id,
+            task,
+            field,
+            text,
+            fuzzy_match,
+        })
+}
+
+/// Renders the search hits with the matched text highlighted, along with each task's list and
+/// Task ID. Hits in the tags or notes show the matching text under the task
+///
+/// Parameters
+/// hits:   The hits to render
+pub fn render_hits(hits: &[SearchHit]) -> String {
+    let mut output = String::new();
+
+    for hit in hits {
+        let highlighted = highlight(&hit.text, &hit.fuzzy_match.positions);
+
+        let description = match hit.field {
+            MatchedField::Description => highlighted.clone(),
+            _ => hit.task.description(),
+        };
+
+        writeln!(
+            output,
+            "{} {}. {} {}",
+            hit.list.bold(),
+            hit.task_id,
+            format!("[{}]", hit.task.status_glyph()).bold(),
+            description
+        )
+        .unwrap();
+
+        match hit.field {
+            MatchedField::Description => (),
+            MatchedField::Tags => writeln!(output, "    tags: {}", highlighted).unwrap(),
+            MatchedField::Notes => writeln!(output, "    notes: {}", highlighted).unwrap(),
+        }
+    }
+
+    output
+}
+
+/// Highlights the characters at the given positions in the text
+///
+/// Parameters
+/// text:        The text to highlight
+/// positions:   The positions of the characters to highlight
+fn highlight(text: &str, positions: &[usize]) -> String {
+    text.chars()
+        .enumerate()
+        .map(
+            |(position, character)| match positions.contains(&position) {
+                true => character.to_string().bright_yellow().bold().to_string(),
+                false => character.to_string(),
+            },
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task;
+
+    use crate::task::TaskStatus;
+
+    #[test]
+    /// Tests if fuzzy_match finds words as is or with their characters in order, and ranks exact
+    /// matches higher
+    fn fuzzy_match_works() {
+        let exact = fuzzy_match("release", "Write the release notes").unwrap();
+
+        assert_eq!(exact.positions, (10..17).collect::<Vec<usize>>());
+
+        let fuzzy = fuzzy_match("rls", "Write the release notes").unwrap();
+
+        assert!(fuzzy.score < exact.score);
+        assert_eq!(
+            fuzzy_match("RELEASE notes", "Write the release notes")
+                .unwrap()
+                .positions
+                .len(),
+            12
+        );
+        assert_eq!(fuzzy_match("xyz", "Write the release notes"), None);
+        assert_eq!(fuzzy_match("  ", "Write the release notes"), None);
+    }
+
+    #[test]
+    /// Tests if search looks through every list, ranking the best matches first and giving each
+    /// hit the Task ID in its own list
+    fn search_ranks_hits_across_lists() {
+        let mut config = Config::new();
+
+        config.add_list(String::from("Dev")).unwrap();
+
+        let mut noted = task("Prepare the demo", TaskStatus::NotStarted, "Main");
+
+        noted.set_notes(Some(String::from("Check the release branch first")));
+
+        let tasks = vec![
+            task("Reply to emails", TaskStatus::NotStarted, "Main"),
+            noted,
+        ];
+
+        let other_tasks = vec![
+            task("Fix the login page", TaskStatus::NotStarted, "Dev"),
+            task("Tag the release", TaskStatus::NotStarted, "Dev"),
+        ];
+
+        let hits = search(&tasks, &other_tasks, &config, "release");
+
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].list, "Dev");
+        assert_eq!(hits[0].task_id, TaskId::from(2));
+        assert_eq!(hits[1].field, MatchedField::Notes);
+        assert_eq!(hits[1].text, "Check the release branch first");
+
+        assert!(search(&tasks, &other_tasks, &config, "nothing like it").is_empty());
+    }
+}
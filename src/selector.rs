@@ -0,0 +1,324 @@
+use crate::task::{normalize_status_name, Task, TaskId};
+
+use crate::task_management::{self, TaskManagementErrors};
+
+use std::str::FromStr;
+
+/// Enum for representing the part of a task a filter expression looks at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    Description,
+    Status,
+    Tag,
+    Priority,
+    Notes,
+}
+
+/// Enum for representing how a filter expression compares the field to its value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOperator {
+    /// The field contains the value, written as ~
+    Contains,
+    /// The field is the value, written as =
+    Equals,
+}
+
+/// Struct for representing a filter expression such as desc~docs or status!=completed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    pub field: FilterField,
+    pub operator: FilterOperator,
+    /// Whether the filter selects the tasks that don't match, written with a ! before the operator
+    pub negated: bool,
+    pub value: String,
+}
+
+impl Filter {
+    /// Returns whether the task matches the filter, ignoring case
+    ///
+    /// Parameters
+    /// task:   The task to check
+    pub fn matches(&self, task: &Task) -> bool {
+        let value = self.value.to_lowercase();
+
+        let compare = |text: &str| {
+            let text = text.to_lowercase();
+
+            match self.operator {
+                FilterOperator::Contains => text.contains(&value),
+                FilterOperator::Equals => text == value,
+            }
+        };
+
+        let matched = match self.field {
+            FilterField::Description => compare(&task.description()),
+            // Statuses are compared by their normalized names, so in-progress matches InProgress
+            FilterField::Status => {
+                let status = normalize_status_name(&task.status().name());
+                let value = normalize_status_name(&self.value);
+
+                match self.operator {
+                    FilterOperator::Contains => status.contains(&value),
+                    FilterOperator::Equals => status == value,
+                }
+            }
+            FilterField::Tag => task.tags().iter().any(|tag| compare(tag)),
+            FilterField::Priority => task
+                .priority()
+                .is_some_and(|priority| compare(&priority.to_string())),
+            FilterField::Notes => task.notes().is_some_and(|notes| compare(&notes)),
+        };
+
+        matched != self.negated
+    }
+}
+
+impl FromStr for Filter {
+    type Err = String;
+
+    /// Parses a filter expression such as desc~docs, made of a field, an operator and a value
+    fn from_str(input: &str) -> Result<Filter, String> {
+        let position = input
+            .find(['~', '='])
+            .ok_or_else(|| format!("'{}' isn't a filter expression, such as desc~docs", input))?;
+
+        let (field, value) = input.split_at(position);
+
+        let operator = match &value[..1] {
+            "~" => FilterOperator::Contains,
+            _ => FilterOperator::Equals,
+        };
+
+        let (field, negated) = match field.strip_suffix('!') {
+            Some(field) => (field, true),
+            None => (field, false),
+        };
+
+        let field = match field.trim().to_lowercase().as_str() {
+            "desc" | "description" => FilterField::Description,
+            "status" => FilterField::Status,
+            "tag" | "tags" => FilterField::Tag,
+            "priority" => FilterField::Priority,
+            "notes" => FilterField::Notes,
+            field => {
+                return Err(format!(
+                    "'{}' can't be filtered on, use desc, status, tag, priority or notes",
+                    field
+                ))
+            }
+        };
+
+        Ok(Filter {
+            field,
+            operator,
+            negated,
+            value: value[1..].trim().to_owned(),
+        })
+    }
+}
+
+/// Enum for representing the ways of choosing tasks in the commands that take Task IDs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskSelector {
+    /// A single task, such as 3 or 3.1
+    Id(TaskId),
+    /// Every task from the first Task ID to the last, which must be at the same level, such as 1-5
+    Range(TaskId, TaskId),
+    /// Every task in the list
+    All,
+    /// Every finished task in the list, which includes cancelled tasks and tasks in a custom status
+    /// that counts as done
+    Completed,
+    /// The last top level task in the list
+    Last,
+    /// Every task matching a filter expression, such as desc~docs
+    Filter(Filter),
+    /// Comma separated selectors, such as 3,7,9
+    Several(Vec<TaskSelector>),
+}
+
+impl TaskSelector {
+    /// Finds the indexes of the tasks the selector chooses, in no particular order and possibly
+    /// with duplicates. Any Task ID that doesn't exist is an error, while a filter that matches
+    /// nothing simply chooses no tasks
+    ///
+    /// Parameters
+    /// tasks:   The task vec to choose from
+    pub fn select(&self, tasks: &[Task]) -> Result<Vec<usize>, TaskManagementErrors> {
+        if tasks.is_empty() {
+            return Err(TaskManagementErrors::EmptyTasklist);
+        }
+
+        let tree = task_management::task_tree(tasks);
+
+        match self {
+            TaskSelector::Id(task_id) => Ok(vec![task_management::find_task(tasks, task_id)?]),
+            TaskSelector::Range(start, end) => (start.number()..=end.number())
+                .map(|number| {
+                    let task_id = match start.parent() {
+                        Some(parent) => parent.child(number),
+                        None => TaskId::from(number),
+                    };
+
+                    task_management::find_task(tasks, &task_id)
+                })
+                .collect(),
+            TaskSelector::All => Ok(tree.into_iter().map(|(_, index)| index).collect()),
+            TaskSelector::Completed => Ok(tree
+                .into_iter()
+                .map(|(_, index)| index)
+                .filter(|index| tasks[*index].is_finished())
+                .collect()),
+            TaskSelector::Last => Ok(tree
+                .into_iter()
+                .filter(|(task_id, _)| task_id.depth() == 0)
+                .map(|(_, index)| index)
+                .next_back()
+                .into_iter()
+                .collect()),
+            TaskSelector::Filter(filter) => Ok(tree
+                .into_iter()
+                .map(|(_, index)| index)
+                .filter(|index| filter.matches(&tasks[*index]))
+                .collect()),
+            TaskSelector::Several(selectors) => {
+                let mut indexes = Vec::new();
+
+                for selector in selectors {
+                    indexes.extend(selector.select(tasks)?);
+                }
+
+                Ok(indexes)
+            }
+        }
+    }
+}
+
+impl From<TaskId> for TaskSelector {
+    fn from(task_id: TaskId) -> TaskSelector {
+        TaskSelector::Id(task_id)
+    }
+}
+
+impl FromStr for TaskSelector {
+    type Err = String;
+
+    /// Parses a Task ID, a range such as 1-5, comma separated selectors such as 3,7,9, one of all,
+    /// completed or last, or a filter expression such as desc~docs
+    fn from_str(input: &str) -> Result<TaskSelector, String> {
+        let input = input.trim();
+
+        // Filter expressions are checked first, as their values may contain commas or dashes
+        if input.contains(['~', '=']) {
+            return input.parse().map(TaskSelector::Filter);
+        }
+
+        if input.contains(',') {
+            return input
+                .split(',')
+                .map(TaskSelector::from_str)
+                .collect::<Result<Vec<TaskSelector>, String>>()
+                .map(TaskSelector::Several);
+        }
+
+        match input.to_lowercase().as_str() {
+            "all" => return Ok(TaskSelector::All),
+            "completed" => return Ok(TaskSelector::Completed),
+            "last" => return Ok(TaskSelector::Last),
+            _ => (),
+        }
+
+        let Some((start, end)) = input.split_once('-') else {
+            return input.parse().map(TaskSelector::Id);
+        };
+
+        let start: TaskId = start.trim().parse()?;
+        let end: TaskId = end.trim().parse()?;
+
+        if start.parent() != end.parent() {
+            return Err(format!(
+                "'{}' isn't a valid range, both Task IDs must be at the same level, such as 3.1-3.4",
+                input
+            ));
+        }
+
+        if start.number() > end.number() {
+            return Err(format!(
+                "'{}' isn't a valid range, the first Task ID must come before the last",
+                input
+            ));
+        }
+
+        Ok(TaskSelector::Range(start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::task::TaskStatus;
+    use crate::test_helpers::task;
+
+    #[test]
+    /// Tests if each kind of selector can be parsed, and invalid ones are rejected
+    fn task_selector_parses() {
+        assert_eq!(
+            "3.1".parse::<TaskSelector>().unwrap(),
+            TaskSelector::Id(TaskId::from(3).child(1))
+        );
+        assert_eq!(
+            "1-5".parse::<TaskSelector>().unwrap(),
+            TaskSelector::Range(TaskId::from(1), TaskId::from(5))
+        );
+        assert_eq!(
+            "3,7".parse::<TaskSelector>().unwrap(),
+            TaskSelector::Several(vec![
+                TaskSelector::Id(TaskId::from(3)),
+                TaskSelector::Id(TaskId::from(7))
+            ])
+        );
+        assert_eq!("ALL".parse::<TaskSelector>().unwrap(), TaskSelector::All);
+        assert_eq!(
+            "status!=completed".parse::<TaskSelector>().unwrap(),
+            TaskSelector::Filter(Filter {
+                field: FilterField::Status,
+                operator: FilterOperator::Equals,
+                negated: true,
+                value: String::from("completed"),
+            })
+        );
+
+        assert!("5-1".parse::<TaskSelector>().is_err());
+        assert!("1-3.1".parse::<TaskSelector>().is_err());
+        assert!("size~big".parse::<TaskSelector>().is_err());
+        assert!("first".parse::<TaskSelector>().is_err());
+    }
+
+    #[test]
+    /// Tests if selectors choose the right tasks, and ranges past the end of the list are errors
+    fn task_selector_selects_tasks() {
+        let mut tasks = vec![
+            task("Write the docs", TaskStatus::Completed, "Main"),
+            task("Fix the login page", TaskStatus::InProgress, "Main"),
+            task("Review the docs", TaskStatus::NotStarted, "Main"),
+            task("Ship", TaskStatus::NotStarted, "Main"),
+            task("Scrapped", TaskStatus::Cancelled, "Main"),
+        ];
+
+        // Tasks in a custom status that counts as done are finished too
+        tasks[3].update_status_as(TaskStatus::Custom(String::from("Done")), true);
+
+        let select = |input: &str| input.parse::<TaskSelector>().unwrap().select(&tasks);
+
+        assert_eq!(select("1-2").unwrap(), vec![0, 1]);
+        assert_eq!(select("completed").unwrap(), vec![0, 3, 4]);
+        assert_eq!(select("last").unwrap(), vec![4]);
+        assert_eq!(select("desc~DOCS").unwrap(), vec![0, 2]);
+        assert_eq!(select("status=in-progress").unwrap(), vec![1]);
+        assert_eq!(select("3,1").unwrap(), vec![2, 0]);
+        assert!(select("tag=urgent").unwrap().is_empty());
+
+        assert_eq!(select("4-6"), Err(TaskManagementErrors::TaskDoesntExist));
+    }
+}
@@ -0,0 +1,236 @@
+use crate::task::{Priority, Task, TaskStatus};
+
+use crate::dates;
+
+use crate::task_management;
+
+use serde::Serialize;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+
+use colored::Colorize;
+
+use std::fmt::Write;
+
+/// The format times are shown in, in the user's timezone
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Struct holding everything about a single task, with other tasks referred to by their Task IDs
+/// rather than their uids
+#[derive(Debug, Serialize)]
+pub struct TaskDetails {
+    task_id: String,
+    list: String,
+    status: TaskStatus,
+    /// The task's position in the list as shown by the tasks command, starting at 1
+    position: usize,
+    list_length: usize,
+    description: String,
+    uid: String,
+    created: Option<DateTime<Utc>>,
+    started: Option<DateTime<Utc>>,
+    completed: Option<DateTime<Utc>>,
+    due: Option<NaiveDate>,
+    planned: Option<NaiveDate>,
+    priority: Option<Priority>,
+    tags: Vec<String>,
+    notes: Option<String>,
+    parent: Option<String>,
+    blocked_by: Vec<String>,
+    recurrence: Option<String>,
+    pomodoros: u32,
+}
+
+impl TaskDetails {
+    /// Collects the details of the task at the given index
+    ///
+    /// Parameters
+    /// tasks:   The vec the task belongs to
+    /// index:   The index of the task
+    pub fn new(tasks: &[Task], index: usize) -> TaskDetails {
+        let tree = task_management::task_tree(tasks);
+
+        // Finds the Task ID of the task with the given uid, if it is in the list
+        let task_id_of = |uid: &String| {
+            tree.iter()
+                .find(|(_, other)| &tasks[*other].uid() == uid)
+                .map(|(task_id, _)| task_id.to_string())
+        };
+
+        let task = &tasks[index];
+
+        let position = tree
+            .iter()
+            .position(|(_, other)| *other == index)
+            .unwrap_or(index);
+
+        TaskDetails {
+            task_id: tree[position].0.to_string(),
+            list: task.list(),
+            status: task.status(),
+            position: position + 1,
+            list_length: tasks.len(),
+            description: task.description(),
+            uid: task.uid(),
+            created: task.created(),
+            started: task.started(),
+            completed: task.completed(),
+            due: task.due(),
+            planned: task.planned(),
+            priority: task.priority(),
+            tags: task.tags(),
+            notes: task.notes(),
+            parent: task.parent().and_then(|parent| task_id_of(&parent)),
+            blocked_by: task.blocked_by().iter().filter_map(task_id_of).collect(),
+            recurrence: task.recurrence().map(|recurrence| recurrence.to_string()),
+            pomodoros: task.pomodoros(),
+        }
+    }
+
+    /// Returns the details as json, for scripts
+    pub fn to_json(&self) -> String {
+        // The details only hold strings, numbers and dates so they can always be serialized
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Returns the details as text, leaving out any fields the task doesn't have
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        writeln!(text, "{}", format!("Task {}", self.task_id).bold()).unwrap();
+
+        let mut field = |name: &str, value: String| {
+            writeln!(text, "{:<14}{}", format!("{}:", name), value).unwrap();
+        };
+
+        field("List", self.list.clone());
+        field("Status", self.status.to_string());
+        field(
+            "Position",
+            format!("{} of {}", self.position, self.list_length),
+        );
+        field("Description", self.description.clone());
+
+        if let Some(created) = self.created {
+            field("Created", local_time(created));
+        }
+
+        if let Some(started) = self.started {
+            field("Started", local_time(started));
+        }
+
+        if let Some(completed) = self.completed {
+            field("Completed", local_time(completed));
+        }
+
+        if let Some(due) = self.due {
+            field("Due", due.format(dates::DATE_FORMAT).to_string());
+        }
+
+        if let Some(planned) = self.planned {
+            field("Planned", planned.format(dates::DATE_FORMAT).to_string());
+        }
+
+        if let Some(priority) = self.priority {
+            field("Priority", priority.to_string());
+        }
+
+        if !self.tags.is_empty() {
+            field("Tags", self.tags.join(" "));
+        }
+
+        if let Some(parent) = &self.parent {
+            field("Subtask of", parent.clone());
+        }
+
+        if !self.blocked_by.is_empty() {
+            field("Blocked by", self.blocked_by.join(", "));
+        }
+
+        if let Some(recurrence) = &self.recurrence {
+            field("Repeats", recurrence.clone());
+        }
+
+        if self.pomodoros > 0 {
+            field("Pomodoros", self.pomodoros.to_string());
+        }
+
+        if !self.uid.is_empty() {
+            field("Uid", self.uid.clone());
+        }
+
+        if let Some(notes) = &self.notes {
+            writeln!(text, "Notes:").unwrap();
+
+            for line in notes.lines() {
+                writeln!(text, "    {}", line).unwrap();
+            }
+        }
+
+        text
+    }
+}
+
+/// Formats the given time in the user's timezone
+///
+/// Parameters
+/// time:   The time to format
+fn local_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format(TIME_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task;
+
+    /// Creates a parent task with a subtask that is blocked by a third task
+    fn tasks() -> Vec<Task> {
+        let mut tasks = vec![
+            task("Release", TaskStatus::NotStarted, "Main"),
+            task("Design", TaskStatus::NotStarted, "Main"),
+            task("Write the changelog", TaskStatus::NotStarted, "Main"),
+        ];
+
+        task_management::assign_uids(&mut tasks);
+
+        let parent = tasks[0].uid();
+        let blocker = tasks[1].uid();
+
+        tasks[2].set_parent(Some(parent));
+        tasks[2].set_blocked_by(vec![blocker]);
+        tasks[2].set_due(NaiveDate::from_ymd_opt(2023, 1, 6));
+        tasks[2].set_notes(Some(String::from("Some notes\nover two lines")));
+
+        tasks
+    }
+
+    #[test]
+    /// Tests if the text details include the task's fields, with other tasks given by Task ID
+    fn to_text_shows_every_field() {
+        let text = TaskDetails::new(&tasks(), 2).to_text();
+
+        assert!(text.contains("Task 1.1"));
+        assert!(text.contains("Position:     2 of 3"));
+        assert!(text.contains("Description:  Write the changelog"));
+        assert!(text.contains("Due:          2023-01-06"));
+        assert!(text.contains("Subtask of:   1"));
+        assert!(text.contains("Blocked by:   2"));
+        assert!(text.contains("    over two lines"));
+        assert!(!text.contains("Priority"));
+    }
+
+    #[test]
+    /// Tests if the json details can be read by scripts
+    fn to_json_works() {
+        let json: serde_json::Value =
+            serde_json::from_str(&TaskDetails::new(&tasks(), 2).to_json()).unwrap();
+
+        assert_eq!(json["task_id"], "1.1");
+        assert_eq!(json["status"], "NotStarted");
+        assert_eq!(json["due"], "2023-01-06");
+        assert_eq!(json["blocked_by"], serde_json::json!(["2"]));
+        assert_eq!(json["priority"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,276 @@
+use crate::task::Task;
+
+use crate::config::Config;
+
+use serde::{Deserialize, Serialize};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+/// Enum for representing what tasks can be sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortField {
+    /// The order of the statuses in the config's workflow
+    Status,
+    Priority,
+    Due,
+    Created,
+    /// The description, alphabetically
+    Alpha,
+}
+
+/// Struct for representing a single key to sort tasks by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortKey {
+    pub field: SortField,
+    pub descending: bool,
+}
+
+impl SortKey {
+    /// Compares two tasks by the key. Tasks missing the field, such as tasks without a due date,
+    /// always go after the tasks that have it
+    ///
+    /// Parameters
+    /// task:     The first task
+    /// other:    The task to compare it to
+    /// config:   The user's config, for the order of the statuses
+    pub fn compare(&self, task: &Task, other: &Task, config: &Config) -> Ordering {
+        let ordering = match self.field {
+            SortField::Status => {
+                let order = config.status_order();
+
+                let position = |task: &Task| {
+                    order
+                        .iter()
+                        .position(|status| status == &task.status())
+                        .unwrap_or(order.len())
+                };
+
+                position(task).cmp(&position(other))
+            }
+            SortField::Priority => return self.compare_optional(task.priority(), other.priority()),
+            SortField::Due => return self.compare_optional(task.due(), other.due()),
+            SortField::Created => return self.compare_optional(task.created(), other.created()),
+            SortField::Alpha => task
+                .description()
+                .to_lowercase()
+                .cmp(&other.description().to_lowercase()),
+        };
+
+        match self.descending {
+            true => ordering.reverse(),
+            false => ordering,
+        }
+    }
+
+    /// Compares two optional values in the key's direction, with missing values last
+    ///
+    /// Parameters
+    /// value:   The first value
+    /// other:   The value to compare it to
+    fn compare_optional<T: Ord>(&self, value: Option<T>, other: Option<T>) -> Ordering {
+        match (value, other) {
+            (Some(value), Some(other)) if self.descending => other.cmp(&value),
+            (Some(value), Some(other)) => value.cmp(&other),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl fmt::Display for SortKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let field = match self.field {
+            SortField::Status => "status",
+            SortField::Priority => "priority",
+            SortField::Due => "due",
+            SortField::Created => "created",
+            SortField::Alpha => "alpha",
+        };
+
+        match self.descending {
+            true => write!(f, "{}:desc", field),
+            false => write!(f, "{}", field),
+        }
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    /// Parses a field such as due, optionally followed by :asc or :desc
+    fn from_str(input: &str) -> Result<SortKey, String> {
+        let input = input.trim().to_lowercase();
+
+        let (field, direction) = input.split_once(':').unwrap_or((&input, "asc"));
+
+        let field = match field {
+            "status" => SortField::Status,
+            "priority" => SortField::Priority,
+            "due" => SortField::Due,
+            "created" => SortField::Created,
+            "alpha" => SortField::Alpha,
+            _ => {
+                return Err(format!(
+                    "'{}' isn't something tasks can be sorted by, use status, priority, due, created or alpha",
+                    field
+                ))
+            }
+        };
+
+        let descending = match direction {
+            "asc" => false,
+            "desc" => true,
+            _ => {
+                return Err(format!(
+                    "'{}' isn't a direction, use asc or desc",
+                    direction
+                ))
+            }
+        };
+
+        Ok(SortKey { field, descending })
+    }
+}
+
+/// Struct for representing the keys tasks are sorted by, in order of importance. An empty order
+/// leaves the tasks in the order they are stored
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct SortOrder(Vec<SortKey>);
+
+impl SortOrder {
+    /// Returns the order the tasks command has always sorted by, which is only by status
+    pub fn by_status() -> SortOrder {
+        SortOrder(vec![SortKey {
+            field: SortField::Status,
+            descending: false,
+        }])
+    }
+
+    /// Returns whether there are no keys to sort by
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Compares two tasks by each key in turn until one tells them apart
+    ///
+    /// Parameters
+    /// task:     The first task
+    /// other:    The task to compare it to
+    /// config:   The user's config, for the order of the statuses
+    pub fn compare(&self, task: &Task, other: &Task, config: &Config) -> Ordering {
+        self.0
+            .iter()
+            .map(|key| key.compare(task, other, config))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "none");
+        }
+
+        let keys: Vec<String> = self.0.iter().map(SortKey::to_string).collect();
+
+        write!(f, "{}", keys.join(","))
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    /// Parses comma separated keys such as status,due:desc, or none for the stored order
+    fn from_str(input: &str) -> Result<SortOrder, String> {
+        if input.trim().to_lowercase() == "none" || input.trim().is_empty() {
+            return Ok(SortOrder::default());
+        }
+
+        input
+            .split(',')
+            .map(SortKey::from_str)
+            .collect::<Result<Vec<SortKey>, String>>()
+            .map(SortOrder)
+    }
+}
+
+impl From<SortOrder> for String {
+    fn from(order: SortOrder) -> String {
+        order.to_string()
+    }
+}
+
+impl TryFrom<String> for SortOrder {
+    type Error = String;
+
+    fn try_from(input: String) -> Result<SortOrder, String> {
+        input.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task;
+
+    use crate::task::{Priority, TaskStatus};
+
+    use chrono::NaiveDate;
+
+    #[test]
+    /// Tests if sort orders can be parsed and displayed
+    fn sort_order_parses_and_displays() {
+        let order: SortOrder = "status, Priority:desc,due:asc".parse().unwrap();
+
+        assert_eq!(order.to_string(), "status,priority:desc,due");
+        assert!("none".parse::<SortOrder>().unwrap().is_empty());
+        assert!("size".parse::<SortOrder>().is_err());
+        assert!("due:sideways".parse::<SortOrder>().is_err());
+    }
+
+    #[test]
+    /// Tests if tasks are compared by each key in turn, with missing values last in either
+    /// direction
+    fn sort_order_compares_by_each_key() {
+        let config = Config::new();
+
+        let mut urgent = task("Urgent", TaskStatus::NotStarted, "Main");
+        urgent.set_priority(Some(Priority::High));
+        urgent.set_due(NaiveDate::from_ymd_opt(2023, 1, 6));
+
+        let mut later = task("Later", TaskStatus::NotStarted, "Main");
+        later.set_priority(Some(Priority::High));
+        later.set_due(NaiveDate::from_ymd_opt(2023, 2, 1));
+
+        let unplanned = task("Unplanned", TaskStatus::NotStarted, "Main");
+
+        let order: SortOrder = "priority:desc,due".parse().unwrap();
+
+        assert_eq!(order.compare(&urgent, &later, &config), Ordering::Less);
+        assert_eq!(
+            order.compare(&unplanned, &urgent, &config),
+            Ordering::Greater
+        );
+
+        let order: SortOrder = "due:desc".parse().unwrap();
+
+        assert_eq!(order.compare(&later, &urgent, &config), Ordering::Less);
+        assert_eq!(
+            order.compare(&unplanned, &urgent, &config),
+            Ordering::Greater
+        );
+
+        let order: SortOrder = "status,alpha".parse().unwrap();
+
+        let done = task("Zebra", TaskStatus::Completed, "Main");
+
+        assert_eq!(order.compare(&done, &later, &config), Ordering::Less);
+        assert_eq!(order.compare(&later, &urgent, &config), Ordering::Less);
+    }
+}
@@ -0,0 +1,351 @@
+use crate::task::{Task, TaskStatus};
+
+use crate::config::Config;
+
+use crate::task_management;
+
+use chrono::{DateTime, Duration, Local, Utc};
+
+use colored::Colorize;
+
+use serde::Serialize;
+
+use std::fmt::Write;
+
+/// The number of characters in each list's progress bar
+const PROGRESS_BAR_WIDTH: usize = 20;
+
+/// Struct for storing the number of tasks in a list with a status
+#[derive(Debug, PartialEq, Eq, Serialize)]
+pub struct StatusCount {
+    pub status: TaskStatus,
+    pub count: usize,
+}
+
+/// Struct for storing how far along a list is
+#[derive(Debug, PartialEq, Serialize)]
+pub struct ListStats {
+    pub list: String,
+    /// The number of tasks with each status, in the order of the config's workflow
+    pub statuses: Vec<StatusCount>,
+    pub total: usize,
+    /// The number of completed or cancelled tasks
+    pub finished: usize,
+    pub percent_complete: f64,
+}
+
+/// Struct for storing how quickly tasks are being finished, counting archived tasks too
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Throughput {
+    pub finished_today: usize,
+    pub finished_last_7_days: usize,
+    pub finished_last_28_days: usize,
+    pub per_day: f64,
+    pub per_week: f64,
+    /// The average time from starting a task to finishing it, for the tasks where both are known
+    pub average_cycle_time_seconds: Option<i64>,
+    /// The number of tasks the average cycle time is taken from
+    pub cycle_time_tasks: usize,
+}
+
+/// Struct for storing the statistics shown by the stats command
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Stats {
+    pub lists: Vec<ListStats>,
+    pub throughput: Throughput,
+}
+
+impl Stats {
+    /// Collects the statistics for every list, along with the throughput of all the tasks
+    /// including the archived ones
+    ///
+    /// Parameters
+    /// tasks:         The vec of tasks in the active list
+    /// other_tasks:   The vec containing tasks not currently in the active list
+    /// archive:       The archived tasks
+    /// config:        The user's config
+    /// now:           The current time, which throughput is measured back from
+    pub fn new(
+        tasks: &[Task],
+        other_tasks: &[Task],
+        archive: &[Task],
+        config: &Config,
+        now: DateTime<Local>,
+    ) -> Stats {
+        let lists = task_management::tasks_by_list(tasks, other_tasks, config)
+            .into_iter()
+            .map(|(list, list_tasks)| list_stats(list, &list_tasks, config))
+            .collect();
+
+        let all_tasks: Vec<&Task> = tasks.iter().chain(other_tasks).chain(archive).collect();
+
+        Stats {
+            lists,
+            throughput: throughput(&all_tasks, now),
+        }
+    }
+
+    /// Returns the statistics as json, for dashboards
+    pub fn to_json(&self) -> String {
+        // The statistics only hold strings and numbers so they can always be serialized
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Returns the statistics as text, with a progress bar for each list
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        for list in &self.lists {
+            writeln!(text, "{}", list.list.bold()).unwrap();
+
+            let counts: Vec<String> = list
+                .statuses
+                .iter()
+                .map(|count| format!("{} {}", count.status, count.count))
+                .collect();
+
+            match counts.is_empty() {
+                true => writeln!(text, "  No tasks").unwrap(),
+                false => writeln!(text, "  {}", counts.join("   ")).unwrap(),
+            }
+
+            writeln!(
+                text,
+                "  {} {:.0}% complete ({} of {})\n",
+                progress_bar(list.percent_complete),
+                list.percent_complete,
+                list.finished,
+                list.total
+            )
+            .unwrap();
+        }
+
+        let throughput = &self.throughput;
+
+        writeln!(
+            text,
+            "{} {} today, {:.1} per day over the last week, {:.1} per week over the last 4 weeks",
+            "Finished:".bold(),
+            throughput.finished_today,
+            throughput.per_day,
+            throughput.per_week
+        )
+        .unwrap();
+
+        let cycle_time = match throughput.average_cycle_time_seconds {
+            Some(seconds) => format!(
+                "{} (from {} tasks)",
+                format_duration(Duration::seconds(seconds)),
+                throughput.cycle_time_tasks
+            ),
+            None => String::from("no tasks have been started and finished yet"),
+        };
+
+        writeln!(text, "{} {}", "Average cycle time:".bold(), cycle_time).unwrap();
+
+        text
+    }
+}
+
+/// Counts the tasks in a list by status
+///
+/// Parameters
+/// list:         The name of the list
+/// list_tasks:   The tasks in the list
+/// config:       The user's config, for the order of the statuses
+fn list_stats(list: String, list_tasks: &[Task], config: &Config) -> ListStats {
+    let mut order = config.status_order();
+
+    // Statuses outside of the workflow are counted after the ones in it
+    for task in list_tasks {
+        if !order.contains(&task.status()) {
+            order.push(task.status());
+        }
+    }
+
+    let statuses = order
+        .into_iter()
+        .map(|status| StatusCount {
+            count: list_tasks
+                .iter()
+                .filter(|task| task.status() == status)
+                .count(),
+            status,
+        })
+        .filter(|count| count.count > 0)
+        .collect();
+
+    let finished = list_tasks.iter().filter(|task| task.is_finished()).count();
+
+    let percent_complete = match list_tasks.len() {
+        0 => 0.0,
+        total => finished as f64 * 100.0 / total as f64,
+    };
+
+    ListStats {
+        list,
+        statuses,
+        total: list_tasks.len(),
+        finished,
+        percent_complete,
+    }
+}
+
+/// Measures how many tasks were finished recently, and how long finished tasks took
+///
+/// Parameters
+/// tasks:   Every task, including the archived ones
+/// now:     The current time
+fn throughput(tasks: &[&Task], now: DateTime<Local>) -> Throughput {
+    let completed: Vec<DateTime<Utc>> = tasks
+        .iter()
+        .filter(|task| task.is_finished())
+        .filter_map(|task| task.completed())
+        .collect();
+
+    let finished_since = |days: i64| {
+        completed
+            .iter()
+            .filter(|completed| now.signed_duration_since(**completed) < Duration::days(days))
+            .count()
+    };
+
+    let finished_today = completed
+        .iter()
+        .filter(|completed| completed.with_timezone(&Local).date_naive() == now.date_naive())
+        .count();
+
+    let cycle_times: Vec<Duration> = tasks
+        .iter()
+        .filter(|task| task.is_finished())
+        .filter_map(|task| Some(task.completed()? - task.started()?))
+        .collect();
+
+    let average_cycle_time_seconds = match cycle_times.len() {
+        0 => None,
+        length => Some(cycle_times.iter().map(Duration::num_seconds).sum::<i64>() / length as i64),
+    };
+
+    Throughput {
+        finished_today,
+        finished_last_7_days: finished_since(7),
+        finished_last_28_days: finished_since(28),
+        per_day: finished_since(7) as f64 / 7.0,
+        per_week: finished_since(28) as f64 / 4.0,
+        average_cycle_time_seconds,
+        cycle_time_tasks: cycle_times.len(),
+    }
+}
+
+/// Draws a bar filled in proportion to the percentage
+///
+/// Parameters
+/// percent:   How far along the bar is, from 0 to 100
+fn progress_bar(percent: f64) -> String {
+    let filled = ((percent / 100.0) * PROGRESS_BAR_WIDTH as f64).round() as usize;
+    let filled = filled.min(PROGRESS_BAR_WIDTH);
+
+    format!(
+        "[{}{}]",
+        "█".repeat(filled).bright_green(),
+        "░".repeat(PROGRESS_BAR_WIDTH - filled).bright_black()
+    )
+}
+
+/// Formats a duration with its two largest units, such as 2d 4h or 3h 12m
+///
+/// Parameters
+/// duration:   The duration to format
+fn format_duration(duration: Duration) -> String {
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    let minutes = duration.num_minutes() % 60;
+
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h", days, hours),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task;
+
+    use chrono::TimeZone;
+
+    /// Creates a finished task that was started and completed the given number of hours ago
+    fn finished_task(now: DateTime<Local>, started: i64, completed: i64) -> Task {
+        let mut task = task("Finished", TaskStatus::Completed, "Main");
+
+        task.set_started(Some((now - Duration::hours(started)).with_timezone(&Utc)));
+        task.set_completed(Some((now - Duration::hours(completed)).with_timezone(&Utc)));
+
+        task
+    }
+
+    #[test]
+    /// Tests if each list's tasks are counted by status, with the percentage finished
+    fn stats_counts_tasks_by_list() {
+        let mut config = Config::new();
+
+        config.add_list(String::from("Dev")).unwrap();
+
+        let tasks = vec![
+            task("Design", TaskStatus::Completed, "Main"),
+            task("Ship", TaskStatus::InProgress, "Main"),
+            task("Scrapped", TaskStatus::Cancelled, "Main"),
+            task("Celebrate", TaskStatus::NotStarted, "Main"),
+        ];
+
+        let stats = Stats::new(&tasks, &[], &[], &config, Local::now());
+
+        assert_eq!(stats.lists.len(), 2);
+        assert_eq!(stats.lists[0].finished, 2);
+        assert_eq!(stats.lists[0].percent_complete, 50.0);
+        assert_eq!(
+            stats.lists[0].statuses[0],
+            StatusCount {
+                status: TaskStatus::Completed,
+                count: 1
+            }
+        );
+        assert_eq!(stats.lists[1].total, 0);
+        assert!(stats.to_text().contains("50% complete (2 of 4)"));
+    }
+
+    #[test]
+    /// Tests if throughput counts archived tasks, and the cycle time is averaged over the tasks
+    /// that were started
+    fn stats_measures_throughput() {
+        let now = Local.with_ymd_and_hms(2023, 1, 10, 12, 0, 0).unwrap();
+
+        let tasks = vec![finished_task(now, 5, 1)];
+
+        let mut unstarted = task("Finished", TaskStatus::Completed, "Main");
+        unstarted.set_completed(Some((now - Duration::days(10)).with_timezone(&Utc)));
+
+        let archive = vec![finished_task(now, 51, 48), unstarted];
+
+        let stats = Stats::new(&tasks, &[], &archive, &Config::new(), now);
+
+        assert_eq!(stats.throughput.finished_today, 1);
+        assert_eq!(stats.throughput.finished_last_7_days, 2);
+        assert_eq!(stats.throughput.finished_last_28_days, 3);
+        assert_eq!(
+            stats.throughput.average_cycle_time_seconds,
+            Some(3600 * 7 / 2)
+        );
+        assert_eq!(stats.throughput.cycle_time_tasks, 2);
+    }
+
+    #[test]
+    /// Tests if durations are shown with their two largest units
+    fn format_duration_works() {
+        assert_eq!(format_duration(Duration::minutes(12)), "12m");
+        assert_eq!(format_duration(Duration::minutes(192)), "3h 12m");
+        assert_eq!(format_duration(Duration::hours(52)), "2d 4h");
+    }
+}
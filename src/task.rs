@@ -1,16 +1,224 @@
 use std::fmt;
+use std::str::FromStr;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use chrono::{DateTime, Days, Months, NaiveDate, Utc};
 
 // Pretty output
-use colored::Colorize;
+use colored::{ColoredString, Colorize};
 
-/// Enum for representing the status of a task
+/// Enum for representing the status of a task. Custom statuses are defined by the user's config,
+/// and are stored by name
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", from = "String")]
 pub enum TaskStatus {
     Completed,
     InProgress,
     NotStarted,
+    Cancelled,
+    Blocked,
+    Custom(String),
+}
+
+impl TaskStatus {
+    /// Returns whether no more work will be done on a task with the status, which is when it has
+    /// been completed or cancelled. Whether a custom status counts as done depends on the config,
+    /// so use Task::is_finished for tasks that may have one
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TaskStatus::Completed | TaskStatus::Cancelled)
+    }
+
+    /// Returns the name the status is stored under
+    pub fn name(&self) -> String {
+        String::from(match self {
+            TaskStatus::Completed => "Completed",
+            TaskStatus::InProgress => "InProgress",
+            TaskStatus::NotStarted => "NotStarted",
+            TaskStatus::Cancelled => "Cancelled",
+            TaskStatus::Blocked => "Blocked",
+            TaskStatus::Custom(name) => name,
+        })
+    }
+
+    /// Parses the name of one of the built in statuses, ignoring case, spaces, dashes and
+    /// underscores, so that not started and not-started both work
+    ///
+    /// Parameters
+    /// name:   The name of the status
+    pub fn from_built_in_name(name: &str) -> Option<TaskStatus> {
+        match normalize_status_name(name).as_str() {
+            "notstarted" => Some(TaskStatus::NotStarted),
+            "inprogress" => Some(TaskStatus::InProgress),
+            "completed" => Some(TaskStatus::Completed),
+            "cancelled" | "canceled" => Some(TaskStatus::Cancelled),
+            "blocked" => Some(TaskStatus::Blocked),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    /// Returns the name of the status as shown to the user, such as Not Started
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskStatus::NotStarted => write!(f, "Not Started"),
+            TaskStatus::InProgress => write!(f, "In Progress"),
+            status => write!(f, "{}", status.name()),
+        }
+    }
+}
+
+/// Lowercases a status name and removes any spaces, dashes and underscores, so that names can be
+/// compared however the user writes them
+///
+/// Parameters
+/// name:   The name of the status
+pub fn normalize_status_name(name: &str) -> String {
+    name.chars()
+        .filter(|character| !matches!(character, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+impl From<TaskStatus> for String {
+    fn from(status: TaskStatus) -> String {
+        status.name()
+    }
+}
+
+impl From<String> for TaskStatus {
+    /// Reads a stored status, where any name other than the built in ones is a custom status
+    fn from(name: String) -> TaskStatus {
+        match name.as_str() {
+            "Completed" => TaskStatus::Completed,
+            "InProgress" => TaskStatus::InProgress,
+            "NotStarted" => TaskStatus::NotStarted,
+            "Cancelled" => TaskStatus::Cancelled,
+            "Blocked" => TaskStatus::Blocked,
+            _ => TaskStatus::Custom(name),
+        }
+    }
+}
+
+/// Enum for representing the priority of a task
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Priority {
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Priority::Low => write!(f, "low"),
+            Priority::Medium => write!(f, "medium"),
+            Priority::High => write!(f, "high"),
+        }
+    }
+}
+
+/// Enum for representing how often a recurring task comes back
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub enum Recurrence {
+    Daily,
+    Weekly,
+    Monthly,
+    EveryNDays(u32),
+}
+
+impl Recurrence {
+    /// Returns the due date of the occurrence after one due on the given date
+    ///
+    /// Parameters
+    /// due:   The due date of the current occurrence
+    pub fn next_due(&self, due: NaiveDate) -> Option<NaiveDate> {
+        self.nth_due(due, 1)
+    }
+
+    /// Returns the due date of the nth occurrence after one due on the given date. Counting from
+    /// the same first date keeps monthly tasks on their day, so one due on the 31st is due on the
+    /// 28th in February but back on the 31st in March
+    ///
+    /// Parameters
+    /// anchor:   The due date of the first occurrence
+    /// n:        The number of occurrences after the first
+    pub fn nth_due(&self, anchor: NaiveDate, n: u32) -> Option<NaiveDate> {
+        match self {
+            Recurrence::Daily => anchor.checked_add_days(Days::new(n as u64)),
+            Recurrence::Weekly => anchor.checked_add_days(Days::new(7 * n as u64)),
+            Recurrence::Monthly => anchor.checked_add_months(Months::new(n)),
+            Recurrence::EveryNDays(days) => {
+                anchor.checked_add_days(Days::new(*days as u64 * n as u64))
+            }
+        }
+    }
+
+    /// Returns whether the date is one of the due dates counted from the anchor
+    ///
+    /// Parameters
+    /// anchor:   The due date of the first occurrence
+    /// due:      The date to check
+    pub fn is_due_on(&self, anchor: NaiveDate, due: NaiveDate) -> bool {
+        (0..)
+            .map_while(|n| self.nth_due(anchor, n))
+            .take_while(|date| *date <= due)
+            .any(|date| date == due)
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recurrence::Daily => write!(f, "daily"),
+            Recurrence::Weekly => write!(f, "weekly"),
+            Recurrence::Monthly => write!(f, "monthly"),
+            Recurrence::EveryNDays(days) => write!(f, "every {} days", days),
+        }
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    /// Parses daily, weekly, monthly, or a number of days such as 3d or every 3 days
+    fn from_str(input: &str) -> Result<Recurrence, String> {
+        let input = input.trim().to_lowercase();
+
+        let days = match input.as_str() {
+            "daily" => return Ok(Recurrence::Daily),
+            "weekly" => return Ok(Recurrence::Weekly),
+            "monthly" => return Ok(Recurrence::Monthly),
+            _ => input
+                .strip_prefix("every ")
+                .and_then(|days| days.strip_suffix(" days"))
+                .or_else(|| input.strip_suffix('d')),
+        };
+
+        match days.map(|days| days.parse::<u32>()) {
+            Some(Ok(days)) if days > 0 => Ok(Recurrence::EveryNDays(days)),
+            _ => Err(format!(
+                "'{}' isn't a valid recurrence, use daily, weekly, monthly or a number of days such as 3d",
+                input
+            )),
+        }
+    }
+}
+
+impl From<Recurrence> for String {
+    fn from(recurrence: Recurrence) -> String {
+        recurrence.to_string()
+    }
+}
+
+impl TryFrom<String> for Recurrence {
+    type Error = String;
+
+    fn try_from(input: String) -> Result<Recurrence, String> {
+        input.parse()
+    }
 }
 
 /// Enum for storing possible errors
@@ -25,7 +233,59 @@ pub enum TaskErrors {
 pub struct Task {
     description: String,
     status: TaskStatus,
-    list: String
+    list: String,
+    // The fields below were added after the tasks file format was first written, so they all
+    // default to allow older tasks files to still be read
+    /// The id that stays the same however the task is moved, used to link subtasks and blockers
+    #[serde(default)]
+    uid: String,
+    /// When the task was added
+    #[serde(default)]
+    created: Option<DateTime<Utc>>,
+    /// When the task was completed, if it has been
+    #[serde(default)]
+    completed: Option<DateTime<Utc>>,
+    /// The day the task is due by
+    #[serde(default)]
+    due: Option<NaiveDate>,
+    /// How important the task is
+    #[serde(default)]
+    priority: Option<Priority>,
+    /// The words the task is tagged with, for filtering
+    #[serde(
+        default,
+        serialize_with = "serialize_words",
+        deserialize_with = "deserialize_words"
+    )]
+    tags: Vec<String>,
+    /// Any notes kept on the task, which can be several lines long
+    #[serde(default)]
+    notes: Option<String>,
+    /// The uid of the task this is a subtask of
+    #[serde(default)]
+    parent: Option<String>,
+    /// The uids of the tasks that must be completed before this task can be started
+    #[serde(
+        default,
+        serialize_with = "serialize_words",
+        deserialize_with = "deserialize_words"
+    )]
+    blocked_by: Vec<String>,
+    /// How often the task comes back once completed, if it is recurring
+    #[serde(default)]
+    recurrence: Option<Recurrence>,
+    /// When the task was last started, which is cleared if it is restarted
+    #[serde(default)]
+    started: Option<DateTime<Utc>>,
+    /// The number of pomodoros completed while focusing on the task
+    #[serde(default)]
+    pomodoros: u32,
+    /// The day the task is planned to be worked on, which puts it on that day's agenda
+    #[serde(default)]
+    planned: Option<NaiveDate>,
+    /// The due date a recurring task's occurrences are counted from
+    #[serde(default)]
+    recurs_from: Option<NaiveDate>,
 }
 
 impl Task {
@@ -48,7 +308,21 @@ impl Task {
         Ok(Task {
             description,
             status,
-            list
+            list,
+            uid: String::new(),
+            created: None,
+            completed: None,
+            due: None,
+            priority: None,
+            tags: Vec::new(),
+            notes: None,
+            parent: None,
+            blocked_by: Vec::new(),
+            recurrence: None,
+            started: None,
+            pomodoros: 0,
+            planned: None,
+            recurs_from: None,
         })
     }
 
@@ -67,6 +341,156 @@ impl Task {
         self.list.clone()
     }
 
+    /// Moves the task to the given list
+    pub fn set_list(&mut self, list: String) {
+        self.list = list;
+    }
+
+    /// Returns a clone of the task's unique id, which is empty until one is assigned
+    pub fn uid(&self) -> String {
+        self.uid.clone()
+    }
+
+    /// Sets the task's unique id
+    pub fn set_uid(&mut self, uid: String) {
+        self.uid = uid;
+    }
+
+    /// Returns when the task was created, if known
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        self.created
+    }
+
+    /// Sets when the task was created
+    pub fn set_created(&mut self, created: Option<DateTime<Utc>>) {
+        self.created = created;
+    }
+
+    /// Returns when the task was started, if it has been
+    pub fn started(&self) -> Option<DateTime<Utc>> {
+        self.started
+    }
+
+    /// Sets when the task was started
+    pub fn set_started(&mut self, started: Option<DateTime<Utc>>) {
+        self.started = started;
+    }
+
+    /// Returns the number of pomodoros completed while focusing on the task
+    pub fn pomodoros(&self) -> u32 {
+        self.pomodoros
+    }
+
+    /// Records a completed pomodoro on the task
+    pub fn add_pomodoro(&mut self) {
+        self.pomodoros += 1;
+    }
+
+    /// Sets the number of pomodoros completed while focusing on the task
+    pub fn set_pomodoros(&mut self, pomodoros: u32) {
+        self.pomodoros = pomodoros;
+    }
+
+    /// Returns when the task was completed, if it has been
+    pub fn completed(&self) -> Option<DateTime<Utc>> {
+        self.completed
+    }
+
+    /// Sets when the task was completed
+    pub fn set_completed(&mut self, completed: Option<DateTime<Utc>>) {
+        self.completed = completed;
+    }
+
+    /// Returns the date the task is due, if it has one
+    pub fn due(&self) -> Option<NaiveDate> {
+        self.due
+    }
+
+    /// Sets the date the task is due
+    pub fn set_due(&mut self, due: Option<NaiveDate>) {
+        self.due = due;
+    }
+
+    /// Returns the day the task is planned for, if it has been planned
+    pub fn planned(&self) -> Option<NaiveDate> {
+        self.planned
+    }
+
+    /// Sets the day the task is planned for
+    pub fn set_planned(&mut self, planned: Option<NaiveDate>) {
+        self.planned = planned;
+    }
+
+    /// Returns the task's priority, if it has one
+    pub fn priority(&self) -> Option<Priority> {
+        self.priority
+    }
+
+    /// Sets the task's priority
+    pub fn set_priority(&mut self, priority: Option<Priority>) {
+        self.priority = priority;
+    }
+
+    /// Returns a clone of the task's tags
+    pub fn tags(&self) -> Vec<String> {
+        self.tags.clone()
+    }
+
+    /// Sets the task's tags
+    pub fn set_tags(&mut self, tags: Vec<String>) {
+        self.tags = tags;
+    }
+
+    /// Returns a clone of the task's notes, if it has any
+    pub fn notes(&self) -> Option<String> {
+        self.notes.clone()
+    }
+
+    /// Sets the task's notes
+    pub fn set_notes(&mut self, notes: Option<String>) {
+        self.notes = notes;
+    }
+
+    /// Returns a clone of the uid of the task this is a subtask of, if it is one
+    pub fn parent(&self) -> Option<String> {
+        self.parent.clone()
+    }
+
+    /// Makes the task a subtask of the task with the given uid
+    pub fn set_parent(&mut self, parent: Option<String>) {
+        self.parent = parent;
+    }
+
+    /// Returns a clone of the uids of the tasks blocking this task
+    pub fn blocked_by(&self) -> Vec<String> {
+        self.blocked_by.clone()
+    }
+
+    /// Sets the uids of the tasks blocking this task
+    pub fn set_blocked_by(&mut self, blocked_by: Vec<String>) {
+        self.blocked_by = blocked_by;
+    }
+
+    /// Returns how often the task comes back, if it is recurring
+    pub fn recurrence(&self) -> Option<Recurrence> {
+        self.recurrence
+    }
+
+    /// Sets how often the task comes back
+    pub fn set_recurrence(&mut self, recurrence: Option<Recurrence>) {
+        self.recurrence = recurrence;
+    }
+
+    /// Returns the due date the task's occurrences are counted from, if it has been recurring
+    pub fn recurs_from(&self) -> Option<NaiveDate> {
+        self.recurs_from
+    }
+
+    /// Sets the due date the task's occurrences are counted from
+    pub fn set_recurs_from(&mut self, recurs_from: Option<NaiveDate>) {
+        self.recurs_from = recurs_from;
+    }
+
     /// Updates the description of the task
     ///
     /// Parameters
@@ -82,26 +506,150 @@ impl Task {
         Ok(())
     }
 
-    /// Updates the status of the task
+    /// Updates the status of the task, recording when it was completed
     ///
     /// Parameters
     /// new_status:   The new status of the task
     pub fn update_status(&mut self, new_status: TaskStatus) {
+        let done = new_status == TaskStatus::Completed;
+
+        self.update_status_as(new_status, done);
+    }
+
+    /// Updates the status of the task, recording when it was started and when it was completed if
+    /// the status counts as done. Used for custom statuses, where the config decides whether they
+    /// are done
+    ///
+    /// Parameters
+    /// new_status:   The new status of the task
+    /// done:         Whether the new status counts as done
+    pub fn update_status_as(&mut self, new_status: TaskStatus, done: bool) {
+        self.completed = match done {
+            true => Some(Utc::now()),
+            false => None,
+        };
+
+        // Only the first start counts, so pausing a task doesn't reset how long it has taken
+        match new_status {
+            TaskStatus::InProgress if self.started.is_none() => self.started = Some(Utc::now()),
+            TaskStatus::NotStarted => self.started = None,
+            _ => (),
+        }
+
         self.status = new_status;
     }
+
+    /// Returns whether no more work will be done on the task. A task with a custom status is
+    /// finished if the status counted as done when it was given, which is when it has a completion
+    /// time
+    pub fn is_finished(&self) -> bool {
+        match &self.status {
+            TaskStatus::Custom(_) => self.completed.is_some(),
+            status => status.is_finished(),
+        }
+    }
+
+    /// Returns the colored glyph shown between the brackets for the task's status
+    pub fn status_glyph(&self) -> ColoredString {
+        match self.status() {
+            TaskStatus::NotStarted => "x".bright_red(),
+            TaskStatus::InProgress => "~".bright_yellow(),
+            TaskStatus::Completed => "✔".bright_green(),
+            TaskStatus::Cancelled => "-".bright_black(),
+            TaskStatus::Blocked => "!".bright_magenta(),
+            // Custom statuses missing from the config
+            TaskStatus::Custom(_) => "?".normal(),
+        }
+    }
+}
+
+/// Serializes a list of words such as tags as a single space separated field, as the csv format
+/// can't hold a list
+fn serialize_words<S: Serializer>(words: &[String], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&words.join(" "))
+}
+
+/// Deserializes the space separated field written by serialize_words back into a list of words
+fn deserialize_words<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    let words = String::deserialize(deserializer)?;
+
+    Ok(words.split_whitespace().map(String::from).collect())
 }
 
 impl fmt::Display for Task {
     /// Returns the task as a 'pretty string'
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let status = format!("[{}]", match self.status() {
-            TaskStatus::NotStarted => "x".bright_red(),
-            TaskStatus::InProgress => "~".bright_yellow(),
-            TaskStatus::Completed => "✔".bright_green(),
-        }).bold();
+        let status = format!("[{}]", self.status_glyph()).bold();
+
+        write!(f, "{} {}", status, self.description())?;
+
+        // Marking tasks with notes, as the notes are only shown by the note command
+        if self.notes.is_some() {
+            write!(f, " {}", "✎".bright_blue())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Struct representing a Task ID as shown by the tasks command. Subtasks have a number for each
+/// level below their top level task, such as 3.1 for the first subtask of task 3
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TaskId(Vec<usize>);
+
+impl TaskId {
+    /// Returns how many levels of subtask the ID is below a top level task
+    pub fn depth(&self) -> usize {
+        self.0.len() - 1
+    }
+
+    /// Returns the task's number among the tasks at its level, such as 1 for 3.1
+    pub fn number(&self) -> usize {
+        self.0[self.0.len() - 1]
+    }
+
+    /// Returns the ID of the task this is a subtask of, or None for a top level task
+    pub fn parent(&self) -> Option<TaskId> {
+        match self.0.len() {
+            1 => None,
+            length => Some(TaskId(self.0[..length - 1].to_vec())),
+        }
+    }
 
-        write!(f, "{} {}", status, self.description())
+    /// Returns the ID of the subtask with the given number below this task
+    pub fn child(&self, number: usize) -> TaskId {
+        let mut numbers = self.0.clone();
+        numbers.push(number);
 
+        TaskId(numbers)
+    }
+}
+
+impl From<usize> for TaskId {
+    fn from(number: usize) -> TaskId {
+        TaskId(vec![number])
+    }
+}
+
+impl FromStr for TaskId {
+    type Err = String;
+
+    /// Parses a Task ID such as 3 or 3.1
+    fn from_str(input: &str) -> Result<TaskId, String> {
+        input
+            .split('.')
+            .map(|number| number.parse::<usize>())
+            .collect::<Result<Vec<usize>, _>>()
+            .map(TaskId)
+            .map_err(|_| format!("'{}' isn't a valid Task ID, such as 3 or 3.1", input))
+    }
+}
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let numbers: Vec<String> = self.0.iter().map(|number| number.to_string()).collect();
+
+        write!(f, "{}", numbers.join("."))
     }
 }
 
@@ -186,6 +734,51 @@ mod tests {
         assert_eq!(task.status(), TaskStatus::InProgress)
     }
 
+    #[test]
+    /// Checks if the update_status method records when the task was completed, and clears it when
+    /// the task is restarted
+    fn update_status_records_completion() {
+        let description = String::from("This is a basic task!");
+
+        let list = String::from("main");
+
+        let mut task = Task::new(description, TaskStatus::NotStarted, list).unwrap();
+
+        task.update_status(TaskStatus::Completed);
+
+        assert!(task.completed().is_some());
+
+        task.update_status(TaskStatus::NotStarted);
+
+        assert!(task.completed().is_none())
+    }
+
+    #[test]
+    /// Checks if the update_status method records when the task was first started, and clears it
+    /// when the task is restarted
+    fn update_status_records_start() {
+        let description = String::from("This is a basic task!");
+
+        let list = String::from("main");
+
+        let mut task = Task::new(description, TaskStatus::NotStarted, list).unwrap();
+
+        task.update_status(TaskStatus::InProgress);
+
+        let started = task.started();
+
+        assert!(started.is_some());
+
+        task.update_status(TaskStatus::Blocked);
+        task.update_status(TaskStatus::InProgress);
+
+        assert_eq!(task.started(), started);
+
+        task.update_status(TaskStatus::NotStarted);
+
+        assert!(task.started().is_none())
+    }
+
     #[test]
     /// Checks if the update_description method works
     fn update_description_works() {
@@ -219,4 +812,74 @@ mod tests {
 
         assert_eq!(err, TaskErrors::EmptyDescription)
     }
+
+    #[test]
+    /// Checks if Task IDs with and without subtask numbers can be parsed and shown again
+    fn task_id_parses_and_displays() {
+        let task_id: TaskId = "3.1".parse().unwrap();
+
+        assert_eq!(task_id, TaskId::from(3).child(1));
+        assert_eq!(task_id.depth(), 1);
+        assert_eq!(task_id.to_string(), "3.1");
+        assert_eq!(task_id.parent(), Some(TaskId::from(3)));
+        assert_eq!(TaskId::from(3).parent(), None);
+        assert_eq!(task_id.number(), 1);
+        assert_eq!("4".parse::<TaskId>().unwrap(), TaskId::from(4));
+        assert!("3.".parse::<TaskId>().is_err());
+        assert!("three".parse::<TaskId>().is_err());
+    }
+
+    #[test]
+    /// Checks if recurrences can be parsed and give the right next due date
+    fn recurrence_parses_and_gives_next_due() {
+        let due = NaiveDate::from_ymd_opt(2023, 1, 31).unwrap();
+
+        let monthly: Recurrence = "monthly".parse().unwrap();
+        let every_three_days: Recurrence = "3d".parse().unwrap();
+
+        assert_eq!(
+            monthly.next_due(due),
+            NaiveDate::from_ymd_opt(2023, 2, 28)
+        );
+        assert_eq!(every_three_days, Recurrence::EveryNDays(3));
+        assert_eq!(
+            every_three_days.to_string().parse::<Recurrence>(),
+            Ok(every_three_days)
+        );
+        assert_eq!(
+            Recurrence::Weekly.next_due(due),
+            NaiveDate::from_ymd_opt(2023, 2, 7)
+        );
+        assert_eq!(monthly.nth_due(due, 2), NaiveDate::from_ymd_opt(2023, 3, 31));
+        assert!(monthly.is_due_on(due, NaiveDate::from_ymd_opt(2023, 2, 28).unwrap()));
+        assert!(!monthly.is_due_on(due, NaiveDate::from_ymd_opt(2023, 3, 28).unwrap()));
+        assert!("0d".parse::<Recurrence>().is_err());
+        assert!("fortnightly".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    /// Checks if statuses are stored by name, and custom statuses only count as finished when
+    /// they were given as done
+    fn custom_statuses_work() {
+        assert_eq!(TaskStatus::from(String::from("InProgress")), TaskStatus::InProgress);
+        assert_eq!(
+            TaskStatus::from(String::from("Review")),
+            TaskStatus::Custom(String::from("Review"))
+        );
+        assert_eq!(String::from(TaskStatus::Custom(String::from("Review"))), "Review");
+        assert_eq!(TaskStatus::from_built_in_name("Not started"), Some(TaskStatus::NotStarted));
+        assert_eq!(TaskStatus::from_built_in_name("Review"), None);
+
+        let description = String::from("A task");
+
+        let list = String::from("main");
+
+        let mut task = Task::new(description, TaskStatus::NotStarted, list).unwrap();
+
+        task.update_status_as(TaskStatus::Custom(String::from("Review")), false);
+        assert!(!task.is_finished());
+
+        task.update_status_as(TaskStatus::Custom(String::from("Done")), true);
+        assert!(task.is_finished());
+    }
 }
@@ -1,9 +1,15 @@
-use crate::task::{Task, TaskErrors, TaskStatus};
+use crate::task::{Task, TaskErrors, TaskId, TaskStatus};
 
 use crate::config::Config;
 
+use crate::sorting::SortOrder;
+
+use crate::wrap;
+
 use colored::Colorize;
 
+use chrono::{NaiveDate, Utc};
+
 use std::fmt::Write;
 
 /// Enum for storing TaskManagementErrors
@@ -12,6 +18,10 @@ pub enum TaskManagementErrors {
     TaskAlreadyGivenStatus,
     TaskDoesntExist,
     EmptyTasklist,
+    TaskIsBlocked,
+    DependencyCycle,
+    TaskNotBlocked,
+    NoTasksSelected,
 }
 
 /// Lists all of the tasks in the tasks vec
@@ -19,8 +29,52 @@ pub enum TaskManagementErrors {
 /// Parameters
 /// tasks:    The vec of tasks to list
 /// config:   The user's config
-pub fn list_tasks(tasks: &Vec<Task>, config: &Config) -> Result<String, TaskManagementErrors> {
-    if tasks.is_empty() {
+/// order:    The order to show the tasks in, without changing their Task IDs
+pub fn list_tasks(
+    tasks: &[Task],
+    config: &Config,
+    order: &SortOrder,
+) -> Result<String, TaskManagementErrors> {
+    render_tasks(tasks, config, order, |_| true)
+}
+
+/// Lists the tasks in the tasks vec that are ready to be started, which are the Not Started tasks
+/// that aren't blocked
+///
+/// Parameters
+/// tasks:    The vec of tasks to list from
+/// config:   The user's config
+/// order:    The order to show the tasks in, without changing their Task IDs
+pub fn list_ready_tasks(
+    tasks: &[Task],
+    config: &Config,
+    order: &SortOrder,
+) -> Result<String, TaskManagementErrors> {
+    render_tasks(tasks, config, order, |index| {
+        tasks[index].status() == TaskStatus::NotStarted && !is_blocked(tasks, index)
+    })
+}
+
+/// Lists the tasks in the tasks vec that the given closure returns true for, with subtasks under
+/// their parents and blocked tasks marked
+///
+/// Parameters
+/// tasks:     The vec of tasks to list
+/// config:    The user's config
+/// order:     The order to show the tasks in, without changing their Task IDs
+/// include:   Returns whether the task at the given index should be listed
+fn render_tasks(
+    tasks: &[Task],
+    config: &Config,
+    order: &SortOrder,
+    include: impl Fn(usize) -> bool,
+) -> Result<String, TaskManagementErrors> {
+    let tree: Vec<(TaskId, usize)> = display_tree(tasks, order, config)
+        .into_iter()
+        .filter(|(_, index)| include(*index))
+        .collect();
+
+    if tree.is_empty() {
         return Err(TaskManagementErrors::EmptyTasklist);
     }
 
@@ -31,57 +85,153 @@ pub fn list_tasks(tasks: &Vec<Task>, config: &Config) -> Result<String, TaskMana
         writeln!(list, "{}", config.current_list().bold()).unwrap();
     }
 
-    let mut task_id = 1;
+    let width = wrap::line_width(config);
 
-    for task in tasks {
-        if config.always_show_task_ids()
-            || (config.smart_task_ids() && tasks.len() >= config.num_of_tasks())
-        {
-            writeln!(list, "{}. {}", task_id, task).unwrap();
-        } else {
-            writeln!(list, "{}", task).unwrap();
-        }
+    let show_task_ids = config.always_show_task_ids()
+        || (config.smart_task_ids() && tasks.len() >= config.num_of_tasks());
+
+    for (task_id, index) in tree {
+        // Subtasks are indented under their parent
+        let indent = "    ".repeat(task_id.depth());
 
-        task_id += 1;
+        let prefix = match show_task_ids {
+            true => format!("{}{}. ", indent, task_id),
+            false => indent,
+        };
+
+        list.push_str(&format_task(tasks, index, config, &prefix, width));
     }
 
     Ok(list)
 }
 
+/// Formats the task for listing after the given prefix, using the glyph and color from the config
+/// if its status is in the workflow. The description is wrapped to the given width, with the
+/// continuation lines indented under the description rather than the prefix or glyph
+///
+/// Parameters
+/// tasks:    The vec the task belongs to
+/// index:    The index of the task
+/// config:   The user's config
+/// prefix:   The indent and Task ID shown before the task
+/// width:    The width to wrap the lines to
+fn format_task(
+    tasks: &[Task],
+    index: usize,
+    config: &Config,
+    prefix: &str,
+    width: usize,
+) -> String {
+    let task = &tasks[index];
+
+    let glyph = match config.status_definition(&task.status()) {
+        Some(definition) => definition.glyph.as_str().color(definition.color.as_str()),
+        None => task.status_glyph(),
+    };
+
+    // The prefix, the glyph in its brackets and a space
+    let start = prefix.chars().count() + glyph.chars().count() + 3;
+
+    let mut lines = wrap::wrap(&task.description(), width.saturating_sub(start));
+
+    // The markers after the description, along with their width without the colors
+    let mut markers = String::new();
+    let mut markers_width = 0;
+
+    // Marking tasks with notes, as the notes are only shown by the note command
+    if task.notes().is_some() {
+        markers.push_str(&format!(" {}", "✎".bright_blue()));
+        markers_width += 2;
+    }
+
+    let blocked = blocked_indicator(tasks, index);
+
+    if !blocked.is_empty() {
+        markers.push_str(&format!(" {}", blocked.bright_red()));
+        markers_width += blocked.chars().count() + 1;
+    }
+
+    // Moving the markers onto their own line if they would run past the width
+    let last_width = lines.last().map_or(0, |line| line.chars().count());
+
+    if !markers.is_empty() && start + last_width + markers_width > width {
+        lines.push(String::new());
+        markers.remove(0);
+    }
+
+    let mut formatted = format!("{}{} ", prefix, format!("[{}]", glyph).bold());
+
+    for (number, line) in lines.iter().enumerate() {
+        if number > 0 {
+            formatted.push_str(&format!("\n{}", " ".repeat(start)));
+        }
+
+        formatted.push_str(line);
+    }
+
+    writeln!(formatted, "{}", markers).unwrap();
+
+    formatted
+}
+
+/// Returns the text shown after a blocked task, giving the Task IDs of the tasks blocking it, or
+/// an empty string if the task isn't blocked
+///
+/// Parameters
+/// tasks:   The vec the task belongs to
+/// index:   The index of the task
+fn blocked_indicator(tasks: &[Task], index: usize) -> String {
+    let blockers = blockers(tasks, index);
+
+    if blockers.is_empty() {
+        return String::new();
+    }
+
+    let blocker_ids: Vec<String> = task_tree(tasks)
+        .into_iter()
+        .filter(|(_, other)| blockers.contains(other))
+        .map(|(task_id, _)| task_id.to_string())
+        .collect();
+
+    format!("(blocked by {})", blocker_ids.join(", "))
+}
+
 /// Lists all of the tasks in the tasks vec
 ///
 /// Parameters
 /// tasks:          The vec of tasks in the active list
 /// other_tasks:    The vec containing tasks not currently in the active list
 /// config:         The user's config
-pub fn list_all_tasks(tasks: &[Task], other_tasks: &[Task], config: &Config) -> Result<String, TaskManagementErrors> {
-    // Creating a vec that contains all of the tasks
-    let mut all_tasks: Vec<Task> = Vec::new();
-    // Populating the vec with all of the tasks
-    all_tasks.extend(tasks.to_vec());
-    all_tasks.extend(other_tasks.to_vec());
-
-    // Returning an error if all_tasks is empty
-    if all_tasks.is_empty() {
+/// order:          The order to show the tasks in each list in, without changing their Task IDs
+pub fn list_all_tasks(
+    tasks: &[Task],
+    other_tasks: &[Task],
+    config: &Config,
+    order: &SortOrder,
+) -> Result<String, TaskManagementErrors> {
+    // Returning an error if there are no tasks in any list
+    if tasks.is_empty() && other_tasks.is_empty() {
         return Err(TaskManagementErrors::EmptyTasklist);
     }
 
     let mut list_of_tasks = String::new();
 
+    let width = wrap::line_width(config);
+
     // Looping through all of the lists in the config
-    for list in config.lists_iter() {
+    for (list, list_tasks) in tasks_by_list(tasks, other_tasks, config) {
         // Printing the current list, and if it is the current list a bright green ✔ will be added
-        if list == &config.current_list() {
-            writeln!(list_of_tasks, "{} {}", list.clone(), "✔".bright_green()).unwrap();
+        if list == config.current_list() {
+            writeln!(list_of_tasks, "{} {}", list, "✔".bright_green()).unwrap();
         } else {
             writeln!(list_of_tasks, "{}", list.bold()).unwrap();
         }
 
-        // Looping through all of the tasks and printing ones that are in the current list
-        for task in all_tasks.iter() {
-            if &task.list() == list {
-                writeln!(list_of_tasks, "{task}").unwrap();
-            }
+        // Printing the tasks that are in the current list, with subtasks under their parent
+        for (task_id, index) in display_tree(&list_tasks, order, config) {
+            let indent = "    ".repeat(task_id.depth());
+
+            list_of_tasks.push_str(&format_task(&list_tasks, index, config, &indent, width));
         }
 
         writeln!(list_of_tasks).unwrap();
@@ -90,48 +240,247 @@ pub fn list_all_tasks(tasks: &[Task], other_tasks: &[Task], config: &Config) ->
     Ok(list_of_tasks)
 }
 
-/// Sorts the given task vec in the order Completed, InProgress, NotStarted
+/// Lists the tasks on the day's agenda across every list, which are the tasks in progress and the
+/// unfinished tasks that are due or planned for the day or earlier. Lists without any of these
+/// tasks are left out
 ///
-/// Parameters:
-/// tasks:   The tasks vec to sort
-pub fn sort_tasks(tasks: &mut Vec<Task>) -> Result<(), TaskManagementErrors> {
-    // Returning an error if the given vec is empty
-    if tasks.is_empty() {
+/// Parameters
+/// tasks:         The vec of tasks in the active list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+/// order:         The order to show the tasks in
+/// today:         The day to show the agenda for
+pub fn list_today_tasks(
+    tasks: &[Task],
+    other_tasks: &[Task],
+    config: &Config,
+    order: &SortOrder,
+    today: NaiveDate,
+) -> Result<String, TaskManagementErrors> {
+    let mut agenda = String::new();
+
+    let width = wrap::line_width(config);
+
+    for (list, list_tasks) in tasks_by_list(tasks, other_tasks, config) {
+        let tree: Vec<(TaskId, usize)> = display_tree(&list_tasks, order, config)
+            .into_iter()
+            .filter(|(_, index)| is_on_agenda(&list_tasks[*index], today))
+            .collect();
+
+        if tree.is_empty() {
+            continue;
+        }
+
+        if list == config.current_list() {
+            writeln!(agenda, "{} {}", list, "✔".bright_green()).unwrap();
+        } else {
+            writeln!(agenda, "{}", list.bold()).unwrap();
+        }
+
+        for (task_id, index) in tree {
+            let indent = "    ".repeat(task_id.depth());
+
+            agenda.push_str(&format_task(&list_tasks, index, config, &indent, width));
+        }
+
+        writeln!(agenda).unwrap();
+    }
+
+    if agenda.is_empty() {
         return Err(TaskManagementErrors::EmptyTasklist);
     }
 
-    // Declaring an array of vecs for sorting
-    let mut sorting_vecs: [Vec<Task>; 3] = Default::default();
+    Ok(agenda)
+}
+
+/// Returns whether the task is on the agenda for the given day, because it is in progress, or it
+/// is unfinished and due or planned for that day or earlier
+///
+/// Parameters
+/// task:    The task to check
+/// today:   The day of the agenda
+fn is_on_agenda(task: &Task, today: NaiveDate) -> bool {
+    if task.status() == TaskStatus::InProgress {
+        return true;
+    }
+
+    !task.is_finished()
+        && (task.due().is_some_and(|due| due <= today)
+            || task.planned().is_some_and(|planned| planned <= today))
+}
+
+/// Moves every unfinished task planned for the given day or earlier on to the next day, returning
+/// how many tasks were moved
+///
+/// Parameters
+/// tasks:   The vec of tasks to roll over
+/// today:   The day being finished
+pub fn roll_planned_tasks(tasks: &mut [Task], today: NaiveDate) -> usize {
+    let tomorrow = today.succ_opt();
+
+    let mut rolled = 0;
 
     for task in tasks.iter_mut() {
-        match task.status() {
-            TaskStatus::Completed => sorting_vecs[0].push(task.clone()),
-            TaskStatus::InProgress => sorting_vecs[1].push(task.clone()),
-            TaskStatus::NotStarted => sorting_vecs[2].push(task.clone()),
+        if !task.is_finished() && task.planned().is_some_and(|planned| planned <= today) {
+            task.set_planned(tomorrow);
+            rolled += 1;
         }
     }
 
-    // Clearing the tasks vec
-    tasks.clear();
+    rolled
+}
+
+/// Groups the tasks of every list in the config, in the order of the lists, keeping each list's
+/// tasks in the order they are stored
+///
+/// Parameters
+/// tasks:         The vec of tasks in the active list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// config:        The user's config
+pub fn tasks_by_list(
+    tasks: &[Task],
+    other_tasks: &[Task],
+    config: &Config,
+) -> Vec<(String, Vec<Task>)> {
+    config
+        .lists_iter()
+        .map(|list| {
+            let list_tasks: Vec<Task> = tasks
+                .iter()
+                .chain(other_tasks)
+                .filter(|task| &task.list() == list)
+                .cloned()
+                .collect();
+
+            (list.clone(), list_tasks)
+        })
+        .collect()
+}
+
+/// Sorts the given task vec in the order of the statuses in the config's workflow, which by
+/// default is Completed, Cancelled, InProgress, Blocked, NotStarted. Tasks keep their order within
+/// a status, and tasks with statuses missing from the workflow go last
+///
+/// Parameters:
+/// tasks:    The tasks vec to sort
+/// config:   The user's config
+pub fn sort_tasks(tasks: &mut [Task], config: &Config) -> Result<(), TaskManagementErrors> {
+    sort_tasks_by(tasks, &SortOrder::by_status(), config)
+}
+
+/// Sorts the given task vec by the keys in the sort order. The sort is stable, so tasks that the
+/// keys don't tell apart keep their order
+///
+/// Parameters:
+/// tasks:    The tasks vec to sort
+/// order:    The keys to sort by
+/// config:   The user's config
+pub fn sort_tasks_by(
+    tasks: &mut [Task],
+    order: &SortOrder,
+    config: &Config,
+) -> Result<(), TaskManagementErrors> {
+    // Returning an error if the given vec is empty
+    if tasks.is_empty() {
+        return Err(TaskManagementErrors::EmptyTasklist);
+    }
 
-    // Adding the tasks back into the given tasks vec in the sorted order
-    tasks.extend(sorting_vecs[0].iter().cloned());
-    tasks.extend(sorting_vecs[1].iter().cloned());
-    tasks.extend(sorting_vecs[2].iter().cloned());
+    tasks.sort_by(|task, other| order.compare(task, other, config));
 
     Ok(())
 }
 
-/// Updates the task at the given index in the task vec to the given status
+/// Returns the Task ID and index of every task as task_tree does, but in the given sort order. The
+/// Task IDs still come from the stored order, so they can be used in other commands, and subtasks
+/// are still shown under their parent
+///
+/// Parameters
+/// tasks:    The tasks to number
+/// order:    The order to show the tasks in
+/// config:   The user's config
+pub fn display_tree(tasks: &[Task], order: &SortOrder, config: &Config) -> Vec<(TaskId, usize)> {
+    let tree = task_tree(tasks);
+
+    if order.is_empty() {
+        return tree;
+    }
+
+    let mut sorted: Vec<usize> = (0..tasks.len()).collect();
+
+    sorted.sort_by(|&index, &other| order.compare(&tasks[index], &tasks[other], config));
+
+    let sorted_tasks: Vec<Task> = sorted.iter().map(|&index| tasks[index].clone()).collect();
+
+    // Building the tree over the sorted tasks puts siblings in the sorted order, and then each
+    // task is given back the Task ID it has in the stored order
+    task_tree(&sorted_tasks)
+        .into_iter()
+        .filter_map(|(_, sorted_index)| {
+            let index = sorted[sorted_index];
+
+            tree.iter()
+                .find(|(_, other)| *other == index)
+                .map(|(task_id, _)| (task_id.clone(), index))
+        })
+        .collect()
+}
+
+/// Updates the task at the given index in the task vec to the given status. Blocked tasks can't be
+/// started or completed unless forced
 ///
 /// Parameters
 /// tasks:        The vec of the task belongs to
 /// index:        The index of the task to update
 /// new_status:   The new status of the task
+/// force:        Whether to update the task even if it is blocked
 pub fn update_task_status(
-    tasks: &mut Vec<Task>,
+    tasks: &mut [Task],
+    index: usize,
+    new_status: TaskStatus,
+    force: bool,
+) -> Result<(), TaskManagementErrors> {
+    let done = new_status == TaskStatus::Completed;
+
+    update_task_status_as(tasks, index, new_status, done, force)
+}
+
+/// Updates the task at the given index in the task vec to the given status, which counts as done
+/// if the config's workflow says so. Tasks with the Blocked status or blocked by an unfinished
+/// task can't be started or given a done status unless forced
+///
+/// Parameters
+/// tasks:        The vec of the task belongs to
+/// index:        The index of the task to update
+/// new_status:   The new status of the task
+/// done:         Whether the new status counts as done
+/// force:        Whether to update the task even if it is blocked
+pub fn update_task_status_as(
+    tasks: &mut [Task],
     index: usize,
     new_status: TaskStatus,
+    done: bool,
+    force: bool,
+) -> Result<(), TaskManagementErrors> {
+    check_status_update(tasks, index, &new_status, done, force)?;
+
+    tasks[index].update_status_as(new_status, done);
+    Ok(())
+}
+
+/// Checks whether the task at the given index can be given the new status, without changing it
+///
+/// Parameters
+/// tasks:        The vec of the task belongs to
+/// index:        The index of the task to check
+/// new_status:   The new status of the task
+/// done:         Whether the new status counts as done
+/// force:        Whether the task may be updated even if it is blocked
+fn check_status_update(
+    tasks: &[Task],
+    index: usize,
+    new_status: &TaskStatus,
+    done: bool,
+    force: bool,
 ) -> Result<(), TaskManagementErrors> {
     if tasks.is_empty() {
         return Err(TaskManagementErrors::EmptyTasklist);
@@ -141,14 +490,64 @@ pub fn update_task_status(
         return Err(TaskManagementErrors::TaskDoesntExist);
     }
 
-    if tasks[index].status() == new_status {
+    if tasks[index].status() == *new_status {
         return Err(TaskManagementErrors::TaskAlreadyGivenStatus);
     }
 
-    tasks[index].update_status(new_status);
+    let starting = done || *new_status == TaskStatus::InProgress;
+
+    let blocked = tasks[index].status() == TaskStatus::Blocked || is_blocked(tasks, index);
+
+    if !force && starting && blocked {
+        return Err(TaskManagementErrors::TaskIsBlocked);
+    }
+
     Ok(())
 }
 
+/// Checks every task at the given indexes before any of them are changed, returning the indexes
+/// of the tasks that should be given the new status. When several tasks were chosen, tasks that
+/// already have the status are skipped rather than being an error, and so are finished tasks
+/// unless they are being restarted
+///
+/// Parameters
+/// tasks:        The vec the tasks belong to
+/// indexes:      The indexes of the chosen tasks
+/// new_status:   The new status of the tasks
+/// done:         Whether the new status counts as done
+/// force:        Whether to update tasks even if they are blocked
+/// several:      Whether the tasks were chosen by a selector that can match several tasks
+pub fn tasks_to_update(
+    tasks: &[Task],
+    indexes: &[usize],
+    new_status: &TaskStatus,
+    done: bool,
+    force: bool,
+    several: bool,
+) -> Result<Vec<usize>, TaskManagementErrors> {
+    let mut to_update = Vec::new();
+
+    for index in indexes.iter().copied() {
+        if several && index < tasks.len() {
+            let task = &tasks[index];
+            let restarting = *new_status == TaskStatus::NotStarted;
+
+            if task.status() == *new_status || (task.is_finished() && !restarting) {
+                continue;
+            }
+        }
+
+        check_status_update(tasks, index, new_status, done, force)?;
+        to_update.push(index);
+    }
+
+    if to_update.is_empty() {
+        return Err(TaskManagementErrors::TaskAlreadyGivenStatus);
+    }
+
+    Ok(to_update)
+}
+
 #[derive(Debug, PartialEq, Eq)]
 /// Enum for representing errors with the update_task_description method. This will probably have
 /// to be refactored/changed/scrapped later, however for now this should work?
@@ -164,7 +563,7 @@ pub enum UpdateTaskErrors {
 /// index:             The index of the task to update
 /// new_description:   The new description of the task
 pub fn update_task_description(
-    tasks: &mut Vec<Task>,
+    tasks: &mut [Task],
     index: usize,
     new_description: String,
 ) -> Result<(), UpdateTaskErrors> {
@@ -187,125 +586,457 @@ pub fn update_task_description(
     }
 }
 
-/// Deletes the task at the given index out of the given Vec<Task>
+/// Deletes the tasks at the given indexes out of the given Vec<Task>, along with their subtasks
 ///
 /// Parameters
-/// tasks:   The vec to remove the task from
-/// index:   The index of the task to remove (0 being the first task)
-pub fn delete_task(tasks: &mut Vec<Task>, index: usize) -> Result<(), TaskManagementErrors> {
-    if index >= tasks.len() {
+/// tasks:     The vec to remove the tasks from
+/// indexes:   The indexes of the tasks to remove
+pub fn delete_tasks(tasks: &mut Vec<Task>, indexes: &[usize]) -> Result<(), TaskManagementErrors> {
+    if indexes.iter().any(|index| *index >= tasks.len()) {
         return Err(TaskManagementErrors::TaskDoesntExist);
     }
 
-    tasks.remove(index);
+    // Removing from the back so that the indexes still to be removed don't shift
+    for index in with_subtasks(tasks, indexes).into_iter().rev() {
+        tasks.remove(index);
+    }
 
     Ok(())
 }
 
-/// Unit Tests
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Returns the given indexes along with the indexes of all of their subtasks, sorted and without
+/// duplicates
+///
+/// Parameters
+/// tasks:     The vec the tasks belong to
+/// indexes:   The indexes of the tasks whose subtasks are wanted
+pub fn with_subtasks(tasks: &[Task], indexes: &[usize]) -> Vec<usize> {
+    let mut selected = indexes.to_vec();
 
-    #[test]
-    /// Tests if the update_task_description works
-    fn update_task_description_works() {
-        let mut tasks_vec: Vec<Task> = vec![
-            Task::new(
-                String::from("A basic task!"),
-                TaskStatus::NotStarted,
-                String::from("main"),
-            )
-            .unwrap(),
-            Task::new(
-                String::from("Another basic task!"),
-                TaskStatus::InProgress,
-                String::from("main"),
-            )
-            .unwrap(),
-        ];
+    let mut next = 0;
 
-        let new_description = String::from("New description");
+    // Each task's children are added as it is reached, so grandchildren are found in turn
+    while next < selected.len() {
+        let uid = tasks[selected[next]].uid();
 
-        update_task_description(&mut tasks_vec, 1, new_description.clone()).unwrap();
+        next += 1;
 
-        assert_eq!(new_description, tasks_vec[1].description());
+        // Tasks without a uid can't have subtasks
+        if uid.is_empty() {
+            continue;
+        }
+
+        for (child, task) in tasks.iter().enumerate() {
+            if task.parent().as_ref() == Some(&uid) && !selected.contains(&child) {
+                selected.push(child);
+            }
+        }
     }
 
-    #[test]
-    /// Tests if the update_task_description returns the right error on an empty new_description
-    fn update_task_description_fails_on_empty_new_description() {
-        let mut tasks_vec: Vec<Task> = vec![
-            Task::new(
-                String::from("A basic task!"),
-                TaskStatus::NotStarted,
-                String::from("main"),
-            )
-            .unwrap(),
-            Task::new(
-                String::from("Another basic task!"),
-                TaskStatus::InProgress,
-                String::from("main"),
-            )
-            .unwrap(),
-        ];
+    selected.sort();
+    selected.dedup();
 
-        let new_description = String::new();
+    selected
+}
 
-        let error = update_task_description(&mut tasks_vec, 1, new_description).unwrap_err();
+/// Gives every task without a unique id one. The ids are made from the current time so that they
+/// are unique across projects, which matters for exports such as calendars
+///
+/// Parameters
+/// tasks:   The tasks to assign ids to
+pub fn assign_uids(tasks: &mut [Task]) {
+    let timestamp = Utc::now().format("%Y%m%dT%H%M%S%f");
 
-        assert_eq!(
-            error,
-            UpdateTaskErrors::TaskErrors(TaskErrors::EmptyDescription)
-        )
+    for (index, task) in tasks.iter_mut().enumerate() {
+        if task.uid().is_empty() {
+            task.set_uid(format!("{}-{}", timestamp, index));
+        }
     }
+}
 
-    #[test]
-    /// Tests if the update_task_description returns the right error on an invalid index
-    fn update_task_description_fails_on_invalid_index() {
-        let mut tasks_vec: Vec<Task> = vec![
-            Task::new(
-                String::from("A basic task!"),
-                TaskStatus::NotStarted,
-                String::from("main"),
-            )
-            .unwrap(),
-            Task::new(
-                String::from("Another basic task!"),
-                TaskStatus::InProgress,
-                String::from("main"),
-            )
-            .unwrap(),
-        ];
+/// Updates the existing tasks that share a unique id with one of the imported tasks, such as when
+/// importing an export of the same project, returning the imported tasks that are new
+///
+/// Parameters
+/// tasks:      The existing tasks
+/// imported:   The imported tasks
+pub fn update_matching_tasks(tasks: &mut [&mut Task], imported: Vec<Task>) -> Vec<Task> {
+    let mut new_tasks = Vec::new();
+
+    for task in imported {
+        let existing = tasks
+            .iter_mut()
+            .find(|existing| !task.uid().is_empty() && existing.uid() == task.uid());
+
+        match existing {
+            Some(existing) => **existing = task,
+            None => new_tasks.push(task),
+        }
+    }
 
-        let new_description = String::from("New description");
+    new_tasks
+}
 
-        let error = update_task_description(&mut tasks_vec, 2, new_description).unwrap_err();
+/// Returns the Task ID and index of every task in the given vec, in the order they are shown with
+/// subtasks following their parent. Tasks whose parent isn't in the vec are treated as top level
+/// tasks
+///
+/// Parameters
+/// tasks:   The tasks to number
+pub fn task_tree(tasks: &[Task]) -> Vec<(TaskId, usize)> {
+    let mut tree = Vec::new();
 
-        assert_eq!(
-            error,
-            UpdateTaskErrors::ManagementErrors(TaskManagementErrors::TaskDoesntExist)
-        )
+    let mut visited = vec![false; tasks.len()];
+
+    let mut number = 0;
+
+    for index in 0..tasks.len() {
+        let is_top_level = match tasks[index].parent() {
+            Some(parent) => !tasks.iter().any(|task| task.uid() == parent),
+            None => true,
+        };
+
+        if is_top_level {
+            number += 1;
+            add_subtree(tasks, index, TaskId::from(number), &mut visited, &mut tree);
+        }
     }
 
-    #[test]
-    /// Tests if the update_task_description returns the right error on an empty vec
-    fn update_task_description_fails_on_empty_vec() {
-        let mut tasks_vec: Vec<Task> = Vec::new();
+    // Tasks that are each other's parents can't be reached from a top level task, so they are
+    // shown as top level tasks instead of being hidden
+    for index in 0..tasks.len() {
+        if !visited[index] {
+            number += 1;
+            add_subtree(tasks, index, TaskId::from(number), &mut visited, &mut tree);
+        }
+    }
 
-        let new_description = String::from("New description");
+    tree
+}
 
-        let error = update_task_description(&mut tasks_vec, 1, new_description).unwrap_err();
+/// Adds the task at the given index and all of its subtasks to the tree built by task_tree
+///
+/// Parameters
+/// tasks:     The tasks being numbered
+/// index:     The index of the task to add
+/// task_id:   The Task ID to give the task
+/// visited:   Which tasks have already been added
+/// tree:      The tree being built
+fn add_subtree(
+    tasks: &[Task],
+    index: usize,
+    task_id: TaskId,
+    visited: &mut [bool],
+    tree: &mut Vec<(TaskId, usize)>,
+) {
+    visited[index] = true;
 
-        assert_eq!(
-            error,
-            UpdateTaskErrors::ManagementErrors(TaskManagementErrors::EmptyTasklist)
-        )
+    let uid = tasks[index].uid();
+
+    tree.push((task_id.clone(), index));
+
+    // Tasks without a uid can't have subtasks
+    if uid.is_empty() {
+        return;
     }
 
-    #[test]
-    /// Tests if the update_task_status function works
-    fn update_task_status_works() {
+    let mut number = 0;
+
+    for child in 0..tasks.len() {
+        if !visited[child] && tasks[child].parent().as_ref() == Some(&uid) {
+            number += 1;
+            add_subtree(tasks, child, task_id.child(number), visited, tree);
+        }
+    }
+}
+
+/// Moves the task at the given index to the given position among the tasks at its level, which
+/// are the other subtasks of its parent, or the other top level tasks for a top level task.
+/// Subtasks move along with their parent. Positions past the last task move it to the end
+///
+/// Parameters
+/// tasks:      The vec the task belongs to
+/// index:      The index of the task to move
+/// position:   The position to move the task to, starting at 1
+pub fn move_task(
+    tasks: &mut Vec<Task>,
+    index: usize,
+    position: usize,
+) -> Result<(), TaskManagementErrors> {
+    if tasks.is_empty() {
+        return Err(TaskManagementErrors::EmptyTasklist);
+    }
+
+    let tree = task_tree(tasks);
+
+    let task_id = match tree.iter().find(|(_, other)| *other == index) {
+        Some((task_id, _)) => task_id.clone(),
+        None => return Err(TaskManagementErrors::TaskDoesntExist),
+    };
+
+    // The indexes of the other tasks at the same level, as they will be once the task is removed
+    let siblings: Vec<usize> = tree
+        .iter()
+        .filter(|(other_id, other)| other_id.parent() == task_id.parent() && *other != index)
+        .map(|(_, other)| match *other > index {
+            true => other - 1,
+            false => *other,
+        })
+        .collect();
+
+    let task = tasks.remove(index);
+
+    // Only the order of tasks at the same level matters for their Task IDs, so the task is placed
+    // just before the sibling it should come before, or just after the last one
+    let target = match siblings.get(position.max(1) - 1) {
+        Some(sibling) => *sibling,
+        None => siblings.last().map_or(index, |sibling| sibling + 1),
+    };
+
+    tasks.insert(target, task);
+
+    Ok(())
+}
+
+/// Returns the index of the task with the given Task ID
+///
+/// Parameters
+/// tasks:     The vec the task belongs to
+/// task_id:   The Task ID of the task
+pub fn find_task(tasks: &[Task], task_id: &TaskId) -> Result<usize, TaskManagementErrors> {
+    if tasks.is_empty() {
+        return Err(TaskManagementErrors::EmptyTasklist);
+    }
+
+    task_tree(tasks)
+        .into_iter()
+        .find(|(other_id, _)| other_id == task_id)
+        .map(|(_, index)| index)
+        .ok_or(TaskManagementErrors::TaskDoesntExist)
+}
+
+/// Completes every task whose subtasks have all been completed or cancelled, as long as at least
+/// one was completed, including tasks that only have finished subtasks because of this
+///
+/// Parameters
+/// tasks:   The vec of tasks to check
+pub fn complete_parents(tasks: &mut [Task]) {
+    loop {
+        let finished_parent = (0..tasks.len()).find(|&index| {
+            let uid = tasks[index].uid();
+
+            let subtasks: Vec<&Task> = tasks
+                .iter()
+                .filter(|task| !uid.is_empty() && task.parent().as_ref() == Some(&uid))
+                .collect();
+
+            !tasks[index].is_finished()
+                && subtasks.iter().all(|task| task.is_finished())
+                && subtasks
+                    .iter()
+                    .any(|task| task.status() != TaskStatus::Cancelled)
+        });
+
+        match finished_parent {
+            Some(index) => tasks[index].update_status(TaskStatus::Completed),
+            None => break,
+        }
+    }
+}
+
+/// Creates the next occurrence of every completed recurring task, due on the first date of its
+/// recurrence after today. The next occurrence takes over the recurrence, so the finished
+/// occurrence can be cleaned up without ending the series. Cancelling a recurring task ends the
+/// series instead, so no occurrence is created for it. Returns how many were created
+///
+/// Parameters
+/// tasks:   The vec of tasks to check, which the occurrences are added to
+/// today:   The day to find the next due date after
+pub fn create_next_occurrences(tasks: &mut Vec<Task>, today: NaiveDate) -> usize {
+    let mut occurrences = Vec::new();
+
+    for task in tasks.iter_mut() {
+        let recurrence = match task.recurrence() {
+            Some(recurrence) if task.is_finished() && task.status() != TaskStatus::Cancelled => {
+                recurrence
+            }
+            _ => continue,
+        };
+
+        let due = task.due().unwrap_or(today);
+
+        // Occurrences are counted from the first due date, unless the due date has since been
+        // moved off the series
+        let anchor = task
+            .recurs_from()
+            .filter(|anchor| recurrence.is_due_on(*anchor, due))
+            .unwrap_or(due);
+
+        // Skipping any occurrences that were missed so that the next one isn't already overdue
+        let next_due = (1..)
+            .map_while(|n| recurrence.nth_due(anchor, n))
+            .find(|next_due| *next_due > due && *next_due > today);
+
+        let Some(next_due) = next_due else {
+            continue;
+        };
+
+        let mut occurrence = task.clone();
+
+        // Only the details of the series are kept, not the progress made on this occurrence
+        occurrence.update_status(TaskStatus::NotStarted);
+        occurrence.set_uid(String::new());
+        occurrence.set_created(Some(Utc::now()));
+        occurrence.set_due(Some(next_due));
+        occurrence.set_recurs_from(Some(anchor));
+        occurrence.set_blocked_by(Vec::new());
+        occurrence.set_notes(None);
+        occurrence.set_pomodoros(0);
+        occurrence.set_planned(None);
+
+        task.set_recurrence(None);
+
+        occurrences.push(occurrence);
+    }
+
+    let created = occurrences.len();
+
+    tasks.extend(occurrences);
+
+    created
+}
+
+/// Returns the indexes of the tasks that block the task at the given index and haven't been
+/// completed or cancelled. Blocking tasks that aren't in the vec don't block the task
+///
+/// Parameters
+/// tasks:   The vec the task belongs to
+/// index:   The index of the task
+pub fn blockers(tasks: &[Task], index: usize) -> Vec<usize> {
+    let blocked_by = tasks[index].blocked_by();
+
+    (0..tasks.len())
+        .filter(|&other| {
+            blocked_by.contains(&tasks[other].uid()) && !tasks[other].is_finished()
+        })
+        .collect()
+}
+
+/// Returns whether the task at the given index is blocked by a task that hasn't been completed
+///
+/// Parameters
+/// tasks:   The vec the task belongs to
+/// index:   The index of the task
+pub fn is_blocked(tasks: &[Task], index: usize) -> bool {
+    !blockers(tasks, index).is_empty()
+}
+
+/// Makes the task at the given index blocked until the task at the blocker index is completed
+///
+/// Parameters
+/// tasks:     The vec the tasks belong to
+/// index:     The index of the task to block
+/// blocker:   The index of the task that must be completed first
+pub fn block_task(
+    tasks: &mut [Task],
+    index: usize,
+    blocker: usize,
+) -> Result<(), TaskManagementErrors> {
+    if index >= tasks.len() || blocker >= tasks.len() {
+        return Err(TaskManagementErrors::TaskDoesntExist);
+    }
+
+    // A task can't wait on itself, or on a task that is already waiting on it
+    if index == blocker || depends_on(tasks, blocker, &tasks[index].uid()) {
+        return Err(TaskManagementErrors::DependencyCycle);
+    }
+
+    let mut blocked_by = tasks[index].blocked_by();
+
+    if !blocked_by.contains(&tasks[blocker].uid()) {
+        blocked_by.push(tasks[blocker].uid());
+    }
+
+    tasks[index].set_blocked_by(blocked_by);
+
+    Ok(())
+}
+
+/// Removes the task at the blocker index from the tasks blocking the task at the given index
+///
+/// Parameters
+/// tasks:     The vec the tasks belong to
+/// index:     The index of the task to unblock
+/// blocker:   The index of the task that no longer blocks it
+pub fn unblock_task(
+    tasks: &mut [Task],
+    index: usize,
+    blocker: usize,
+) -> Result<(), TaskManagementErrors> {
+    if index >= tasks.len() || blocker >= tasks.len() {
+        return Err(TaskManagementErrors::TaskDoesntExist);
+    }
+
+    let mut blocked_by = tasks[index].blocked_by();
+
+    let uid = tasks[blocker].uid();
+
+    if !blocked_by.contains(&uid) {
+        return Err(TaskManagementErrors::TaskNotBlocked);
+    }
+
+    blocked_by.retain(|other| other != &uid);
+
+    tasks[index].set_blocked_by(blocked_by);
+
+    Ok(())
+}
+
+/// Returns whether the task at the given index waits on the task with the given uid, directly or
+/// through the tasks blocking it
+///
+/// Parameters
+/// tasks:   The vec the tasks belong to
+/// index:   The index of the task to start from
+/// uid:     The uid of the task to look for
+fn depends_on(tasks: &[Task], index: usize, uid: &String) -> bool {
+    let mut visited = vec![false; tasks.len()];
+
+    let mut to_visit = vec![index];
+
+    while let Some(current) = to_visit.pop() {
+        if visited[current] {
+            continue;
+        }
+
+        visited[current] = true;
+
+        let blocked_by = tasks[current].blocked_by();
+
+        if blocked_by.contains(uid) {
+            return true;
+        }
+
+        to_visit.extend((0..tasks.len()).filter(|&other| blocked_by.contains(&tasks[other].uid())));
+    }
+
+    false
+}
+
+/// Unit Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::StatusDefinition;
+
+    use crate::task::Recurrence;
+
+    use crate::test_helpers::task;
+
+    #[test]
+    /// Tests if the update_task_description works
+    fn update_task_description_works() {
         let mut tasks_vec: Vec<Task> = vec![
             Task::new(
                 String::from("A basic task!"),
@@ -321,15 +1052,16 @@ mod tests {
             .unwrap(),
         ];
 
-        update_task_status(&mut tasks_vec, 1, TaskStatus::Completed).unwrap();
+        let new_description = String::from("New description");
 
-        assert_eq!(tasks_vec[1].status(), TaskStatus::Completed)
+        update_task_description(&mut tasks_vec, 1, new_description.clone()).unwrap();
+
+        assert_eq!(new_description, tasks_vec[1].description());
     }
 
     #[test]
-    /// Tests if the update_task_status function returns the appropriate error if the task is the
-    /// given status already
-    fn update_task_status_fails_when_already_at_given_status() {
+    /// Tests if the update_task_description returns the right error on an empty new_description
+    fn update_task_description_fails_on_empty_new_description() {
         let mut tasks_vec: Vec<Task> = vec![
             Task::new(
                 String::from("A basic task!"),
@@ -345,15 +1077,19 @@ mod tests {
             .unwrap(),
         ];
 
-        let err = update_task_status(&mut tasks_vec, 1, TaskStatus::InProgress).unwrap_err();
+        let new_description = String::new();
 
-        assert_eq!(err, TaskManagementErrors::TaskAlreadyGivenStatus)
+        let error = update_task_description(&mut tasks_vec, 1, new_description).unwrap_err();
+
+        assert_eq!(
+            error,
+            UpdateTaskErrors::TaskErrors(TaskErrors::EmptyDescription)
+        )
     }
 
     #[test]
-    /// Tests if the update_task_status function returns the appropriate error if the given index
-    /// is out of range of the vec
-    fn update_task_status_fails_when_index_out_of_range() {
+    /// Tests if the update_task_description returns the right error on an invalid index
+    fn update_task_description_fails_on_invalid_index() {
         let mut tasks_vec: Vec<Task> = vec![
             Task::new(
                 String::from("A basic task!"),
@@ -363,30 +1099,40 @@ mod tests {
             .unwrap(),
             Task::new(
                 String::from("Another basic task!"),
-                TaskStatus::NotStarted,
+                TaskStatus::InProgress,
                 String::from("main"),
             )
             .unwrap(),
         ];
 
-        let err = update_task_status(&mut tasks_vec, 3, TaskStatus::Completed).unwrap_err();
+        let new_description = String::from("New description");
 
-        assert_eq!(err, TaskManagementErrors::TaskDoesntExist)
+        let error = update_task_description(&mut tasks_vec, 2, new_description).unwrap_err();
+
+        assert_eq!(
+            error,
+            UpdateTaskErrors::ManagementErrors(TaskManagementErrors::TaskDoesntExist)
+        )
     }
 
     #[test]
-    /// Test if the update_task_status function returns the appropriate error if the vec is empty
-    fn update_task_status_fails_on_empty_vec() {
+    /// Tests if the update_task_description returns the right error on an empty vec
+    fn update_task_description_fails_on_empty_vec() {
         let mut tasks_vec: Vec<Task> = Vec::new();
 
-        let err = update_task_status(&mut tasks_vec, 1, TaskStatus::Completed).unwrap_err();
+        let new_description = String::from("New description");
 
-        assert_eq!(err, TaskManagementErrors::EmptyTasklist)
+        let error = update_task_description(&mut tasks_vec, 1, new_description).unwrap_err();
+
+        assert_eq!(
+            error,
+            UpdateTaskErrors::ManagementErrors(TaskManagementErrors::EmptyTasklist)
+        )
     }
 
     #[test]
-    /// Tests if the delete_task function works
-    fn delete_task_works() {
+    /// Tests if the update_task_status function works
+    fn update_task_status_works() {
         let mut tasks_vec: Vec<Task> = vec![
             Task::new(
                 String::from("A basic task!"),
@@ -396,28 +1142,45 @@ mod tests {
             .unwrap(),
             Task::new(
                 String::from("Another basic task!"),
-                TaskStatus::NotStarted,
+                TaskStatus::InProgress,
                 String::from("main"),
             )
             .unwrap(),
         ];
 
-        delete_task(&mut tasks_vec, 1).unwrap();
+        update_task_status(&mut tasks_vec, 1, TaskStatus::Completed, false).unwrap();
 
-        assert_eq!(
-            tasks_vec,
-            vec![Task::new(
+        assert_eq!(tasks_vec[1].status(), TaskStatus::Completed)
+    }
+
+    #[test]
+    /// Tests if the update_task_status function returns the appropriate error if the task is the
+    /// given status already
+    fn update_task_status_fails_when_already_at_given_status() {
+        let mut tasks_vec: Vec<Task> = vec![
+            Task::new(
                 String::from("A basic task!"),
                 TaskStatus::NotStarted,
-                String::from("main")
+                String::from("main"),
             )
-            .unwrap()]
-        )
+            .unwrap(),
+            Task::new(
+                String::from("Another basic task!"),
+                TaskStatus::InProgress,
+                String::from("main"),
+            )
+            .unwrap(),
+        ];
+
+        let err = update_task_status(&mut tasks_vec, 1, TaskStatus::InProgress, false).unwrap_err();
+
+        assert_eq!(err, TaskManagementErrors::TaskAlreadyGivenStatus)
     }
 
     #[test]
-    /// Tests if the delete_task function errors when the index passed to it is out of range
-    fn delete_task_errors_on_invalid_index() {
+    /// Tests if the update_task_status function returns the appropriate error if the given index
+    /// is out of range of the vec
+    fn update_task_status_fails_when_index_out_of_range() {
         let mut tasks_vec: Vec<Task> = vec![
             Task::new(
                 String::from("A basic task!"),
@@ -433,9 +1196,19 @@ mod tests {
             .unwrap(),
         ];
 
-        let error = delete_task(&mut tasks_vec, 2).unwrap_err();
+        let err = update_task_status(&mut tasks_vec, 3, TaskStatus::Completed, false).unwrap_err();
+
+        assert_eq!(err, TaskManagementErrors::TaskDoesntExist)
+    }
+
+    #[test]
+    /// Test if the update_task_status function returns the appropriate error if the vec is empty
+    fn update_task_status_fails_on_empty_vec() {
+        let mut tasks_vec: Vec<Task> = Vec::new();
 
-        assert_eq!(error, TaskManagementErrors::TaskDoesntExist)
+        let err = update_task_status(&mut tasks_vec, 1, TaskStatus::Completed, false).unwrap_err();
+
+        assert_eq!(err, TaskManagementErrors::EmptyTasklist)
     }
 
     #[test]
@@ -447,7 +1220,7 @@ mod tests {
 
         let tasks_vec: Vec<Task> = Vec::new();
 
-        let error = list_tasks(&tasks_vec, &config).unwrap_err();
+        let error = list_tasks(&tasks_vec, &config, &SortOrder::default()).unwrap_err();
 
         assert_eq!(error, TaskManagementErrors::EmptyTasklist)
     }
@@ -461,11 +1234,92 @@ mod tests {
 
         let other_tasks: Vec<Task> = Vec::new();
 
-        let error = list_all_tasks(&tasks, &other_tasks, &config).unwrap_err();
+        let error =
+            list_all_tasks(&tasks, &other_tasks, &config, &SortOrder::default()).unwrap_err();
 
         assert_eq!(error, TaskManagementErrors::EmptyTasklist)
     }
 
+    #[test]
+    /// Tests if imported tasks update the existing tasks with the same uid, and the rest are new
+    fn update_matching_tasks_works() {
+        let mut existing = task("Old description", TaskStatus::NotStarted, "Main");
+        existing.set_uid(String::from("1"));
+
+        let mut updated = task("New description", TaskStatus::Completed, "Main");
+        updated.set_uid(String::from("1"));
+
+        let mut new_task = task("New task", TaskStatus::NotStarted, "Main");
+        new_task.set_uid(String::from("2"));
+
+        let new_tasks =
+            update_matching_tasks(&mut [&mut existing], vec![updated.clone(), new_task.clone()]);
+
+        assert_eq!(existing, updated);
+        assert_eq!(new_tasks, vec![new_task]);
+    }
+
+    #[test]
+    /// Tests if the agenda gathers the tasks in progress, due or planned from every list, leaving
+    /// out lists without any
+    fn list_today_tasks_works() {
+        let mut config = Config::new();
+
+        config.add_list(String::from("Dev")).unwrap();
+        config.add_list(String::from("Home")).unwrap();
+
+        let today = NaiveDate::from_ymd_opt(2023, 1, 10).unwrap();
+
+        let mut tasks = vec![
+            task("Write the docs", TaskStatus::InProgress, "Main"),
+            task("Someday", TaskStatus::NotStarted, "Main"),
+        ];
+
+        let mut other_tasks = vec![
+            task("Fix the login page", TaskStatus::NotStarted, "Dev"),
+            task("Shipped", TaskStatus::Completed, "Dev"),
+            task("Water the plants", TaskStatus::NotStarted, "Home"),
+        ];
+
+        tasks[1].set_planned(today.succ_opt());
+        other_tasks[0].set_due(today.pred_opt());
+        other_tasks[1].set_planned(Some(today));
+
+        let agenda =
+            list_today_tasks(&tasks, &other_tasks, &config, &SortOrder::default(), today).unwrap();
+
+        assert!(agenda.contains("Write the docs"));
+        assert!(agenda.contains("Fix the login page"));
+        assert!(!agenda.contains("Someday"));
+        assert!(!agenda.contains("Shipped"));
+        assert!(!agenda.contains("Home"));
+
+        let error = list_today_tasks(&[], &[], &config, &SortOrder::default(), today).unwrap_err();
+
+        assert_eq!(error, TaskManagementErrors::EmptyTasklist)
+    }
+
+    #[test]
+    /// Tests if only unfinished tasks planned for the day or earlier are rolled to the next day
+    fn roll_planned_tasks_works() {
+        let today = NaiveDate::from_ymd_opt(2023, 1, 10).unwrap();
+
+        let mut tasks = vec![
+            task("Overdue plan", TaskStatus::NotStarted, "Main"),
+            task("Done", TaskStatus::Completed, "Main"),
+            task("Next week", TaskStatus::NotStarted, "Main"),
+        ];
+
+        tasks[0].set_planned(today.pred_opt());
+        tasks[1].set_planned(Some(today));
+        tasks[2].set_planned(NaiveDate::from_ymd_opt(2023, 1, 17));
+
+        assert_eq!(roll_planned_tasks(&mut tasks, today), 1);
+        assert_eq!(tasks[0].planned(), today.succ_opt());
+        assert_eq!(tasks[1].planned(), Some(today));
+        assert_eq!(tasks[2].planned(), NaiveDate::from_ymd_opt(2023, 1, 17));
+    }
+
     #[test]
     /// Tests if the sort_tasks function works
     fn sort_tasks_works() {
@@ -496,7 +1350,7 @@ mod tests {
             .unwrap(),
         ];
 
-        sort_tasks(&mut tasks).unwrap();
+        sort_tasks(&mut tasks, &Config::new()).unwrap();
 
         assert_eq!(
             tasks,
@@ -534,8 +1388,546 @@ mod tests {
     fn sort_tasks_fails_on_empty_vec() {
         let mut tasks = Vec::new();
 
-        let error = sort_tasks(&mut tasks).unwrap_err();
+        let error = sort_tasks(&mut tasks, &Config::new()).unwrap_err();
 
         assert_eq!(error, TaskManagementErrors::EmptyTasklist)
     }
+
+    #[test]
+    /// Tests if assign_uids gives every task without a uid a unique one, and leaves existing ones
+    fn assign_uids_works() {
+        let mut tasks = vec![
+            Task::new(
+                String::from("A basic task!"),
+                TaskStatus::NotStarted,
+                String::from("main"),
+            )
+            .unwrap(),
+            Task::new(
+                String::from("Another basic task!"),
+                TaskStatus::NotStarted,
+                String::from("main"),
+            )
+            .unwrap(),
+        ];
+
+        tasks[0].set_uid(String::from("existing"));
+
+        assign_uids(&mut tasks);
+
+        assert_eq!(tasks[0].uid(), "existing");
+        assert!(!tasks[1].uid().is_empty());
+
+        tasks.push(tasks[1].clone());
+        tasks[2].set_uid(String::new());
+
+        assign_uids(&mut tasks);
+
+        assert_ne!(tasks[1].uid(), tasks[2].uid());
+    }
+
+    /// Creates tasks for the subtask tests, where task 1 has the subtasks 1.1 and 1.2, and 1.2 has
+    /// the subtask 1.2.1
+    fn tasks_with_subtasks() -> Vec<Task> {
+        let mut tasks: Vec<Task> = ["Parent", "Other", "First child", "Second child", "Grandchild"]
+            .iter()
+            .map(|description| {
+                Task::new(
+                    String::from(*description),
+                    TaskStatus::NotStarted,
+                    String::from("main"),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        assign_uids(&mut tasks);
+
+        let parent = tasks[0].uid();
+        let second_child = tasks[3].uid();
+
+        tasks[2].set_parent(Some(parent.clone()));
+        tasks[3].set_parent(Some(parent));
+        tasks[4].set_parent(Some(second_child));
+
+        tasks
+    }
+
+    #[test]
+    /// Tests if deleting a task deletes its subtasks too, rather than leaving them without a parent
+    fn delete_tasks_deletes_subtasks() {
+        let mut tasks = tasks_with_subtasks();
+
+        assert_eq!(with_subtasks(&tasks, &[3]), vec![3, 4]);
+
+        delete_tasks(&mut tasks, &[0]).unwrap();
+
+        let descriptions: Vec<String> = tasks.iter().map(Task::description).collect();
+
+        assert_eq!(descriptions, vec!["Other"]);
+    }
+
+    #[test]
+    /// Tests if task_tree numbers subtasks under their parents
+    fn task_tree_numbers_subtasks() {
+        let tasks = tasks_with_subtasks();
+
+        let tree: Vec<(String, usize)> = task_tree(&tasks)
+            .into_iter()
+            .map(|(task_id, index)| (task_id.to_string(), index))
+            .collect();
+
+        assert_eq!(
+            tree,
+            vec![
+                (String::from("1"), 0),
+                (String::from("1.1"), 2),
+                (String::from("1.2"), 3),
+                (String::from("1.2.1"), 4),
+                (String::from("2"), 1),
+            ]
+        );
+
+        assert_eq!(find_task(&tasks, &"1.2.1".parse().unwrap()), Ok(4));
+        assert_eq!(
+            find_task(&tasks, &"2.1".parse().unwrap()),
+            Err(TaskManagementErrors::TaskDoesntExist)
+        );
+    }
+
+    #[test]
+    /// Tests if task_tree still shows tasks that are each other's parents
+    fn task_tree_handles_parent_cycles() {
+        let mut tasks = tasks_with_subtasks();
+
+        let grandchild = tasks[4].uid();
+
+        tasks[3].set_parent(Some(grandchild));
+
+        assert_eq!(task_tree(&tasks).len(), tasks.len());
+    }
+
+    #[test]
+    /// Tests if complete_parents completes parents once all of their subtasks are completed
+    fn complete_parents_works() {
+        let mut tasks = tasks_with_subtasks();
+
+        tasks[2].update_status(TaskStatus::Completed);
+
+        complete_parents(&mut tasks);
+
+        assert_eq!(tasks[0].status(), TaskStatus::NotStarted);
+
+        tasks[4].update_status(TaskStatus::Completed);
+
+        complete_parents(&mut tasks);
+
+        assert_eq!(tasks[3].status(), TaskStatus::Completed);
+        assert_eq!(tasks[0].status(), TaskStatus::Completed);
+        assert_eq!(tasks[1].status(), TaskStatus::NotStarted);
+    }
+
+    /// Creates three tasks with uids for the dependency tests
+    fn tasks_with_uids() -> Vec<Task> {
+        let mut tasks: Vec<Task> = ["Design", "Implement", "Release"]
+            .iter()
+            .map(|description| {
+                Task::new(
+                    String::from(*description),
+                    TaskStatus::NotStarted,
+                    String::from("main"),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        assign_uids(&mut tasks);
+
+        tasks
+    }
+
+    #[test]
+    /// Tests if blocked tasks can't be started until their blocker is completed, unless forced
+    fn update_task_status_refuses_blocked_tasks() {
+        let mut tasks = tasks_with_uids();
+
+        block_task(&mut tasks, 1, 0).unwrap();
+
+        assert!(is_blocked(&tasks, 1));
+        assert_eq!(
+            update_task_status(&mut tasks, 1, TaskStatus::InProgress, false),
+            Err(TaskManagementErrors::TaskIsBlocked)
+        );
+
+        update_task_status(&mut tasks, 0, TaskStatus::Completed, false).unwrap();
+
+        assert!(!is_blocked(&tasks, 1));
+        assert_eq!(
+            update_task_status(&mut tasks, 1, TaskStatus::InProgress, false),
+            Ok(())
+        );
+
+        block_task(&mut tasks, 2, 1).unwrap();
+
+        assert_eq!(
+            update_task_status(&mut tasks, 2, TaskStatus::Completed, true),
+            Ok(())
+        );
+    }
+
+    #[test]
+    /// Tests if tasks with the Blocked status can't be started or finished unless forced, but can
+    /// be restarted
+    fn update_task_status_refuses_tasks_with_the_blocked_status() {
+        let mut tasks = tasks_with_uids();
+
+        update_task_status(&mut tasks, 0, TaskStatus::Blocked, false).unwrap();
+
+        assert_eq!(
+            update_task_status(&mut tasks, 0, TaskStatus::InProgress, false),
+            Err(TaskManagementErrors::TaskIsBlocked)
+        );
+        assert_eq!(
+            update_task_status(&mut tasks, 0, TaskStatus::Completed, false),
+            Err(TaskManagementErrors::TaskIsBlocked)
+        );
+        assert_eq!(
+            update_task_status(&mut tasks, 0, TaskStatus::InProgress, true),
+            Ok(())
+        );
+
+        update_task_status(&mut tasks, 1, TaskStatus::Blocked, false).unwrap();
+
+        assert_eq!(
+            update_task_status(&mut tasks, 1, TaskStatus::NotStarted, false),
+            Ok(())
+        );
+    }
+
+    #[test]
+    /// Tests if block_task refuses dependencies that would wait on each other
+    fn block_task_detects_cycles() {
+        let mut tasks = tasks_with_uids();
+
+        block_task(&mut tasks, 1, 0).unwrap();
+        block_task(&mut tasks, 2, 1).unwrap();
+
+        assert_eq!(
+            block_task(&mut tasks, 0, 2),
+            Err(TaskManagementErrors::DependencyCycle)
+        );
+        assert_eq!(
+            block_task(&mut tasks, 0, 0),
+            Err(TaskManagementErrors::DependencyCycle)
+        );
+    }
+
+    #[test]
+    /// Tests if unblock_task removes the dependency, and errors if there isn't one
+    fn unblock_task_works() {
+        let mut tasks = tasks_with_uids();
+
+        block_task(&mut tasks, 1, 0).unwrap();
+        unblock_task(&mut tasks, 1, 0).unwrap();
+
+        assert!(!is_blocked(&tasks, 1));
+        assert_eq!(
+            unblock_task(&mut tasks, 1, 0),
+            Err(TaskManagementErrors::TaskNotBlocked)
+        );
+    }
+
+    #[test]
+    /// Tests if list_ready_tasks only lists Not Started tasks that aren't blocked
+    fn list_ready_tasks_works() {
+        let mut tasks = tasks_with_uids();
+
+        block_task(&mut tasks, 1, 0).unwrap();
+        tasks[2].update_status(TaskStatus::InProgress);
+
+        let list = list_ready_tasks(&tasks, &Config::new(), &SortOrder::default()).unwrap();
+
+        assert!(list.contains("Design"));
+        assert!(!list.contains("Implement"));
+        assert!(!list.contains("Release"));
+    }
+
+    #[test]
+    /// Tests if completing a recurring task creates its next occurrence, which takes over the
+    /// recurrence
+    fn create_next_occurrences_works() {
+        let today = NaiveDate::from_ymd_opt(2023, 1, 4).unwrap();
+
+        let mut tasks = tasks_with_uids();
+
+        tasks[0].set_recurrence(Some(Recurrence::Weekly));
+        tasks[0].set_due(NaiveDate::from_ymd_opt(2023, 1, 2));
+        tasks[1].set_recurrence(Some(Recurrence::Daily));
+
+        assert_eq!(create_next_occurrences(&mut tasks, today), 0);
+
+        tasks[0].update_status(TaskStatus::Completed);
+
+        assert_eq!(create_next_occurrences(&mut tasks, today), 1);
+        assert_eq!(tasks.len(), 4);
+        assert_eq!(tasks[0].recurrence(), None);
+        assert_eq!(tasks[3].description(), "Design");
+        assert_eq!(tasks[3].status(), TaskStatus::NotStarted);
+        assert_eq!(tasks[3].recurrence(), Some(Recurrence::Weekly));
+        assert_eq!(tasks[3].due(), NaiveDate::from_ymd_opt(2023, 1, 9));
+
+        // The completed occurrence no longer creates occurrences
+        assert_eq!(create_next_occurrences(&mut tasks, today), 0);
+    }
+
+    #[test]
+    /// Tests if the next occurrence starts afresh, and monthly tasks stay on their day of the month
+    fn create_next_occurrences_resets_occurrences() {
+        let mut tasks = tasks_with_uids();
+
+        tasks[0].set_recurrence(Some(Recurrence::Monthly));
+        tasks[0].set_due(NaiveDate::from_ymd_opt(2023, 1, 31));
+        tasks[0].set_notes(Some(String::from("Went well")));
+        tasks[0].set_planned(NaiveDate::from_ymd_opt(2023, 1, 30));
+        tasks[0].add_pomodoro();
+        tasks[0].update_status(TaskStatus::Completed);
+
+        create_next_occurrences(&mut tasks, NaiveDate::from_ymd_opt(2023, 1, 31).unwrap());
+
+        let february = tasks.last_mut().unwrap();
+
+        assert_eq!(february.due(), NaiveDate::from_ymd_opt(2023, 2, 28));
+        assert_eq!(february.notes(), None);
+        assert_eq!(february.planned(), None);
+        assert_eq!(february.pomodoros(), 0);
+        assert_eq!(february.completed(), None);
+
+        february.update_status(TaskStatus::Completed);
+
+        create_next_occurrences(&mut tasks, NaiveDate::from_ymd_opt(2023, 2, 28).unwrap());
+
+        assert_eq!(tasks.last().unwrap().due(), NaiveDate::from_ymd_opt(2023, 3, 31));
+    }
+
+    #[test]
+    /// Tests if cancelling a recurring task ends the series rather than creating an occurrence
+    fn create_next_occurrences_skips_cancelled_tasks() {
+        let mut tasks = tasks_with_uids();
+
+        tasks[0].set_recurrence(Some(Recurrence::Daily));
+        tasks[0].update_status(TaskStatus::Cancelled);
+
+        let today = NaiveDate::from_ymd_opt(2023, 1, 4).unwrap();
+
+        assert_eq!(create_next_occurrences(&mut tasks, today), 0);
+        assert_eq!(tasks.len(), 3);
+    }
+
+    #[test]
+    /// Tests if sort_tasks places cancelled tasks after completed ones, and blocked tasks before
+    /// not started ones
+    fn sort_tasks_places_cancelled_and_blocked_tasks() {
+        let statuses = [
+            TaskStatus::NotStarted,
+            TaskStatus::Blocked,
+            TaskStatus::InProgress,
+            TaskStatus::Cancelled,
+            TaskStatus::Completed,
+        ];
+
+        let mut tasks: Vec<Task> = statuses
+            .iter()
+            .map(|status| {
+                Task::new(
+                    String::from("A basic task"),
+                    status.clone(),
+                    String::from("main"),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        sort_tasks(&mut tasks, &Config::new()).unwrap();
+
+        let sorted: Vec<TaskStatus> = tasks.iter().map(|task| task.status()).collect();
+
+        assert_eq!(
+            sorted,
+            vec![
+                TaskStatus::Completed,
+                TaskStatus::Cancelled,
+                TaskStatus::InProgress,
+                TaskStatus::Blocked,
+                TaskStatus::NotStarted,
+            ]
+        );
+    }
+
+    #[test]
+    /// Tests if sort_tasks follows the order of the config's workflow, with statuses missing from
+    /// the workflow last
+    fn sort_tasks_follows_the_workflow() {
+        let mut config = Config::new();
+
+        config.set_statuses(
+            ["Backlog", "Doing", "Done"]
+                .iter()
+                .map(|name| StatusDefinition {
+                    name: String::from(*name),
+                    glyph: name[..1].to_owned(),
+                    color: String::from("blue"),
+                    done: *name == "Done",
+                    active: *name == "Doing",
+                })
+                .collect(),
+        );
+
+        let statuses = [
+            TaskStatus::Custom(String::from("Done")),
+            TaskStatus::NotStarted,
+            TaskStatus::Custom(String::from("Doing")),
+            TaskStatus::Custom(String::from("Backlog")),
+        ];
+
+        let mut tasks: Vec<Task> = statuses
+            .iter()
+            .map(|status| {
+                Task::new(String::from("A basic task"), status.clone(), String::from("main"))
+                    .unwrap()
+            })
+            .collect();
+
+        sort_tasks(&mut tasks, &config).unwrap();
+
+        let sorted: Vec<TaskStatus> = tasks.iter().map(|task| task.status()).collect();
+
+        assert_eq!(
+            sorted,
+            vec![
+                TaskStatus::Custom(String::from("Backlog")),
+                TaskStatus::Custom(String::from("Doing")),
+                TaskStatus::Custom(String::from("Done")),
+                TaskStatus::NotStarted,
+            ]
+        );
+    }
+
+    #[test]
+    /// Tests if list_tasks wraps long descriptions to the wrap_width, with continuation lines
+    /// indented under the description
+    fn list_tasks_wraps_descriptions() {
+        let mut config = Config::new();
+
+        config.set_always_show_task_ids(true);
+        config.set_wrap_width(30);
+
+        let tasks = vec![Task::new(
+            String::from("Look into wrapping the print out of tasks to 80 characters"),
+            TaskStatus::NotStarted,
+            String::from("Main"),
+        )
+        .unwrap()];
+
+        let list = list_tasks(&tasks, &config, &SortOrder::default()).unwrap();
+
+        let lines: Vec<&str> = list.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].ends_with("Look into wrapping the"));
+        assert_eq!(lines[1], "       print out of tasks to");
+        assert_eq!(lines[2], "       80 characters");
+    }
+
+    #[test]
+    /// Tests if move_task moves top level tasks with their subtasks, and subtasks among the
+    /// other subtasks of their parent
+    fn move_task_works() {
+        let mut tasks = tasks_with_subtasks();
+
+        let tree = |tasks: &[Task]| -> Vec<String> {
+            task_tree(tasks)
+                .into_iter()
+                .map(|(task_id, index)| format!("{} {}", task_id, tasks[index].description()))
+                .collect()
+        };
+
+        move_task(&mut tasks, 1, 1).unwrap();
+
+        assert_eq!(
+            tree(&tasks),
+            vec![
+                "1 Other",
+                "2 Parent",
+                "2.1 First child",
+                "2.2 Second child",
+                "2.2.1 Grandchild",
+            ]
+        );
+
+        let first_child = find_task(&tasks, &"2.1".parse().unwrap()).unwrap();
+
+        move_task(&mut tasks, first_child, 10).unwrap();
+
+        assert_eq!(
+            tree(&tasks),
+            vec![
+                "1 Other",
+                "2 Parent",
+                "2.1 Second child",
+                "2.1.1 Grandchild",
+                "2.2 First child",
+            ]
+        );
+    }
+
+    #[test]
+    /// Tests if sort_tasks keeps the order tasks were moved into within each status
+    fn sort_tasks_keeps_manual_order() {
+        let mut tasks: Vec<Task> = ["First", "Second", "Third"]
+            .iter()
+            .map(|description| {
+                Task::new(
+                    String::from(*description),
+                    TaskStatus::NotStarted,
+                    String::from("main"),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        tasks[1].update_status(TaskStatus::InProgress);
+
+        move_task(&mut tasks, 2, 1).unwrap();
+
+        sort_tasks(&mut tasks, &Config::new()).unwrap();
+
+        let descriptions: Vec<String> = tasks.iter().map(|task| task.description()).collect();
+
+        assert_eq!(descriptions, vec!["Second", "Third", "First"]);
+    }
+
+    #[test]
+    /// Tests if display_tree shows tasks in the sort order with subtasks under their parent,
+    /// keeping the Task IDs of the stored order
+    fn display_tree_keeps_task_ids() {
+        let tasks = tasks_with_subtasks();
+
+        let order: SortOrder = "alpha:desc".parse().unwrap();
+
+        let tree: Vec<String> = display_tree(&tasks, &order, &Config::new())
+            .into_iter()
+            .map(|(task_id, index)| format!("{} {}", task_id, tasks[index].description()))
+            .collect();
+
+        assert_eq!(
+            tree,
+            vec![
+                "1 Parent",
+                "1.2 Second child",
+                "1.2.1 Grandchild",
+                "1.1 First child",
+                "2 Other",
+            ]
+        );
+    }
 }
@@ -0,0 +1,39 @@
+use crate::task::{Task, TaskStatus};
+
+use chrono::NaiveDate;
+
+/// Creates a task with the given status in the given list for the tests
+///
+/// Parameters
+/// description:   The description of the task
+/// status:        The status of the task
+/// list:          The list the task belongs to
+pub fn task(description: &str, status: TaskStatus, list: &str) -> Task {
+    Task::new(description.to_owned(), status, list.to_owned()).unwrap()
+}
+
+/// Creates a task with the given status and uid in the given list, for tests that need to find the
+/// task again by its uid
+///
+/// Parameters
+/// description:   The description of the task
+/// status:        The status of the task
+/// list:          The list the task belongs to
+/// uid:           The uid to give the task
+pub fn task_with_uid(description: &str, status: TaskStatus, list: &str, uid: &str) -> Task {
+    let mut task = task(description, status, list);
+
+    task.set_uid(uid.to_owned());
+
+    task
+}
+
+/// Creates a date, which must be valid, for the tests
+///
+/// Parameters
+/// year:    The year of the date
+/// month:   The month of the date, starting at 1
+/// day:     The day of the month, starting at 1
+pub fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
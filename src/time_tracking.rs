@@ -0,0 +1,394 @@
+use crate::task::Task;
+
+use crate::config::Config;
+
+use chrono::{DateTime, Duration, Utc};
+
+use colored::Colorize;
+
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Write;
+
+/// Struct for storing a period of time spent on a task. Sessions without an end are still running,
+/// and are saved that way so that the timer keeps going between commands
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeSession {
+    /// The uid of the task the time was spent on
+    pub task_uid: String,
+    /// The task's list and description when the session started, used if the task is deleted
+    pub list: String,
+    pub description: String,
+    pub start: DateTime<Utc>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl TimeSession {
+    /// Returns how long the session has lasted, counting a running session up to now
+    ///
+    /// Parameters
+    /// since:   Only time after this is counted, if given
+    /// now:     The current time
+    pub fn duration(&self, since: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Duration {
+        let start = match since {
+            Some(since) => self.start.max(since),
+            None => self.start,
+        };
+
+        (self.end.unwrap_or(now) - start).max(Duration::zero())
+    }
+}
+
+/// Struct for storing the time spent on a task, for the time report
+#[derive(Debug, PartialEq, Eq)]
+pub struct TaskTime {
+    pub list: String,
+    pub description: String,
+    pub sessions: usize,
+    pub duration: Duration,
+    /// Whether the task's timer is still running
+    pub running: bool,
+}
+
+/// Returns whether a timer is running for the task with the given uid
+///
+/// Parameters
+/// sessions:   The recorded sessions
+/// uid:        The uid of the task
+pub fn is_running(sessions: &[TimeSession], uid: &str) -> bool {
+    sessions
+        .iter()
+        .any(|session| session.task_uid == uid && session.end.is_none())
+}
+
+/// Starts a timer for the task, unless one is already running. Tasks without a uid can't be timed,
+/// as the sessions couldn't be matched back to them
+///
+/// Parameters
+/// sessions:   The recorded sessions
+/// task:       The task to time
+/// now:        The current time
+pub fn start_timer(sessions: &mut Vec<TimeSession>, task: &Task, now: DateTime<Utc>) -> bool {
+    if task.uid().is_empty() || is_running(sessions, &task.uid()) {
+        return false;
+    }
+
+    sessions.push(TimeSession {
+        task_uid: task.uid(),
+        list: task.list(),
+        description: task.description(),
+        start: now,
+        end: None,
+    });
+
+    true
+}
+
+/// Stops the running timers of the tasks with the given uids, returning how many were stopped
+///
+/// Parameters
+/// sessions:   The recorded sessions
+/// uids:       The uids of the tasks to stop timing
+/// now:        The current time
+pub fn stop_timers(sessions: &mut [TimeSession], uids: &[String], now: DateTime<Utc>) -> usize {
+    let mut stopped = 0;
+
+    for session in sessions.iter_mut() {
+        if session.end.is_none() && uids.contains(&session.task_uid) {
+            session.end = Some(now);
+            stopped += 1;
+        }
+    }
+
+    stopped
+}
+
+/// Stops the timers of any tasks that are no longer in an active status, such as tasks that have
+/// been finished, cancelled, restarted or deleted, so a timer only runs while its task is being
+/// worked on
+///
+/// Parameters
+/// sessions:   The recorded sessions
+/// tasks:      Every task
+/// config:     The user's config, which says which custom statuses are active
+/// now:        The current time
+pub fn stop_idle_timers(
+    sessions: &mut [TimeSession],
+    tasks: &[Task],
+    config: &Config,
+    now: DateTime<Utc>,
+) {
+    let idle: Vec<String> = sessions
+        .iter()
+        .filter(|session| session.end.is_none())
+        .filter(|session| {
+            !tasks.iter().any(|task| {
+                task.uid() == session.task_uid && config.status_is_active(&task.status())
+            })
+        })
+        .map(|session| session.task_uid.clone())
+        .collect();
+
+    stop_timers(sessions, &idle, now);
+}
+
+/// Adds up the time spent on each task, in the order the tasks were first worked on. Tasks are
+/// shown with their current list and description, or those from when they were timed if they no
+/// longer exist
+///
+/// Parameters
+/// sessions:   The recorded sessions
+/// tasks:      Every task, including the archived ones
+/// list:       The list to report on, or None for every list
+/// since:      Only time after this is counted, if given
+/// now:        The current time
+pub fn time_spent(
+    sessions: &[TimeSession],
+    tasks: &[&Task],
+    list: Option<&str>,
+    since: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Vec<TaskTime> {
+    let mut times: Vec<(String, TaskTime)> = Vec::new();
+
+    for session in sessions {
+        let duration = session.duration(since, now);
+
+        // Sessions that ended before the report starts don't count
+        if since.is_some() && duration.is_zero() {
+            continue;
+        }
+
+        let (task_list, description) =
+            match tasks.iter().find(|task| task.uid() == session.task_uid) {
+                Some(task) => (task.list(), task.description()),
+                None => (session.list.clone(), session.description.clone()),
+            };
+
+        if list.is_some_and(|list| list != task_list) {
+            continue;
+        }
+
+        match times.iter_mut().find(|(uid, _)| uid == &session.task_uid) {
+            Some((_, time)) => {
+                time.sessions += 1;
+                time.duration += duration;
+                time.running |= session.end.is_none();
+            }
+            None => times.push((
+                session.task_uid.clone(),
+                TaskTime {
+                    list: task_list,
+                    description,
+                    sessions: 1,
+                    duration,
+                    running: session.end.is_none(),
+                },
+            )),
+        }
+    }
+
+    times.into_iter().map(|(_, time)| time).collect()
+}
+
+/// Formats a duration as hours and minutes, such as 1h 05m
+///
+/// Parameters
+/// duration:   The duration to format
+fn format_hours(duration: Duration) -> String {
+    format!(
+        "{}h {:02}m",
+        duration.num_hours(),
+        duration.num_minutes() % 60
+    )
+}
+
+/// Renders the time spent on each task grouped by list, with a total for each list and overall
+///
+/// Parameters
+/// times:   The time spent on each task
+pub fn render_report(times: &[TaskTime]) -> String {
+    let mut report = String::new();
+
+    let mut lists: Vec<&String> = Vec::new();
+
+    for time in times {
+        if !lists.contains(&&time.list) {
+            lists.push(&time.list);
+        }
+    }
+
+    for list in lists {
+        writeln!(report, "{}", list.bold()).unwrap();
+
+        let list_times: Vec<&TaskTime> = times.iter().filter(|time| &time.list == list).collect();
+
+        for time in &list_times {
+            let running = match time.running {
+                true => format!(" {}", "(running)".bright_green()),
+                false => String::new(),
+            };
+
+            writeln!(
+                report,
+                "  {:>9}  {}{}",
+                format_hours(time.duration),
+                time.description,
+                running
+            )
+            .unwrap();
+        }
+
+        let total: Duration = list_times.iter().map(|time| time.duration).sum();
+
+        writeln!(report, "  {:>9}  {}\n", format_hours(total), "total".bold()).unwrap();
+    }
+
+    let total: Duration = times.iter().map(|time| time.duration).sum();
+
+    writeln!(report, "{} {}", "Total:".bold(), format_hours(total)).unwrap();
+
+    report
+}
+
+/// Renders the time spent on each task as csv, with the hours as a decimal for invoicing
+///
+/// Parameters
+/// times:   The time spent on each task
+pub fn render_csv(times: &[TaskTime]) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    // Writing to a vec can't fail, so the results are ignored
+    let _ = writer.write_record(["list", "task", "sessions", "hours"]);
+
+    for time in times {
+        let _ = writer.write_record([
+            time.list.clone(),
+            time.description.clone(),
+            time.sessions.to_string(),
+            format!("{:.2}", time.duration.num_seconds() as f64 / 3600.0),
+        ]);
+    }
+
+    let bytes = writer.into_inner().unwrap_or_default();
+
+    String::from_utf8(bytes).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task_with_uid;
+
+    use crate::config::StatusDefinition;
+    use crate::task::TaskStatus;
+
+    use chrono::TimeZone;
+
+    /// Returns the given hour of the 10th of January 2023, in UTC
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2023, 1, 10, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    /// Tests if timers only run once per task, and are stopped once their task isn't in progress
+    fn timers_start_and_stop() {
+        let mut sessions = Vec::new();
+
+        let mut tasks = vec![
+            task_with_uid("Write the docs", TaskStatus::InProgress, "Main", "1"),
+            task_with_uid("Ship", TaskStatus::InProgress, "Main", "2"),
+        ];
+
+        assert!(start_timer(&mut sessions, &tasks[0], at(9)));
+        assert!(!start_timer(&mut sessions, &tasks[0], at(10)));
+        assert!(start_timer(&mut sessions, &tasks[1], at(10)));
+
+        tasks[0].update_status(TaskStatus::Completed);
+
+        stop_idle_timers(&mut sessions, &tasks, &Config::new(), at(11));
+
+        assert!(!is_running(&sessions, "1"));
+        assert!(is_running(&sessions, "2"));
+        assert_eq!(sessions[0].end, Some(at(11)));
+
+        assert_eq!(stop_timers(&mut sessions, &[String::from("2")], at(12)), 1);
+        assert!(!is_running(&sessions, "2"));
+    }
+
+    #[test]
+    /// Tests if timers keep running for tasks in a custom status marked active, and only those
+    fn active_custom_statuses_keep_timers() {
+        let mut config = Config::new();
+
+        config.set_statuses(
+            [("Doing", true), ("Review", false)]
+                .iter()
+                .map(|(name, active)| StatusDefinition {
+                    name: String::from(*name),
+                    glyph: name[..1].to_owned(),
+                    color: String::from("blue"),
+                    done: false,
+                    active: *active,
+                })
+                .collect(),
+        );
+
+        let mut sessions = Vec::new();
+
+        let mut tasks = vec![
+            task_with_uid("Write the docs", TaskStatus::InProgress, "Main", "1"),
+            task_with_uid("Ship", TaskStatus::InProgress, "Main", "2"),
+        ];
+
+        start_timer(&mut sessions, &tasks[0], at(9));
+        start_timer(&mut sessions, &tasks[1], at(9));
+
+        tasks[0].update_status(TaskStatus::Custom(String::from("Doing")));
+        tasks[1].update_status(TaskStatus::Custom(String::from("Review")));
+
+        stop_idle_timers(&mut sessions, &tasks, &config, at(10));
+
+        assert!(is_running(&sessions, "1"));
+        assert!(!is_running(&sessions, "2"));
+    }
+
+    #[test]
+    /// Tests if the time spent is added up per task, counting running timers up to now and only
+    /// the time after since
+    fn time_spent_adds_up_sessions() {
+        let docs = task_with_uid("Write the docs", TaskStatus::InProgress, "Main", "1");
+        let ship = task_with_uid("Ship", TaskStatus::InProgress, "Dev", "2");
+
+        let session = |task: &Task, start: u32, end: Option<u32>| TimeSession {
+            task_uid: task.uid(),
+            list: task.list(),
+            description: task.description(),
+            start: at(start),
+            end: end.map(at),
+        };
+
+        let sessions = vec![
+            session(&docs, 8, Some(9)),
+            session(&ship, 9, Some(11)),
+            session(&docs, 12, None),
+        ];
+
+        let times = time_spent(&sessions, &[&docs, &ship], None, None, at(14));
+
+        assert_eq!(times.len(), 2);
+        assert_eq!(times[0].duration, Duration::hours(3));
+        assert_eq!(times[0].sessions, 2);
+        assert!(times[0].running);
+
+        let times = time_spent(&sessions, &[&docs], Some("Dev"), Some(at(10)), at(14));
+
+        assert_eq!(times.len(), 1);
+        assert_eq!(times[0].duration, Duration::hours(1));
+
+        let csv = render_csv(&times);
+
+        assert_eq!(csv, "list,task,sessions,hours\nDev,Ship,1,1.00\n");
+    }
+}
@@ -0,0 +1,721 @@
+use crate::task::{Task, TaskId, TaskStatus};
+
+use crate::args::{AddCommand, DeleteCommand, FinishCommand, RestartCommand, StartCommand};
+
+use crate::config::Config;
+
+use crate::confirm;
+
+use crate::selector::TaskSelector;
+
+use crate::task_management;
+
+use crate::time_tracking::TimeSession;
+
+use ratatui::backend::Backend;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{Frame, Terminal};
+
+use std::io;
+
+/// The keys shown at the bottom of the screen when there is no message to show
+const HELP: &str =
+    "↑/↓ task  ←/→ list  s start  f finish  r restart  d delete  m move  a add  q quit";
+
+/// Enum for representing what the keys currently do
+#[derive(Debug, PartialEq, Eq)]
+enum Mode {
+    /// Keys act on the selected task
+    Normal,
+    /// Keys are typed into the description of a new task
+    Adding(String),
+    /// Keys choose the list to move the selected task to, storing the index of the chosen list
+    Moving(usize),
+    /// Keys answer whether to delete the selected task, storing the question to show
+    Deleting(String),
+}
+
+/// Struct for storing the state of the full screen interface
+pub struct App<'a> {
+    tasks: &'a mut Vec<Task>,
+    other_tasks: &'a mut Vec<Task>,
+    sessions: &'a mut Vec<TimeSession>,
+    config: &'a mut Config,
+    assume_yes: bool,
+    selected: usize,
+    mode: Mode,
+    message: Option<String>,
+    running: bool,
+}
+
+impl<'a> App<'a> {
+    /// Creates the interface over the current list, with the first task selected
+    ///
+    /// Parameters
+    /// tasks:         The task vec of the current list
+    /// other_tasks:   The vec containing tasks not currently in the active list
+    /// sessions:      The recorded time tracking sessions
+    /// config:        The user's config
+    /// assume_yes:    Whether to delete tasks without asking the user to confirm
+    pub fn new(
+        tasks: &'a mut Vec<Task>,
+        other_tasks: &'a mut Vec<Task>,
+        sessions: &'a mut Vec<TimeSession>,
+        config: &'a mut Config,
+        assume_yes: bool,
+    ) -> App<'a> {
+        App {
+            tasks,
+            other_tasks,
+            sessions,
+            config,
+            assume_yes,
+            selected: 0,
+            mode: Mode::Normal,
+            message: None,
+            running: true,
+        }
+    }
+
+    /// Draws the interface to the terminal and handles key presses until the user quits
+    ///
+    /// Parameters
+    /// terminal:   The terminal to draw to
+    pub fn run<B: Backend>(&mut self, terminal: &mut Terminal<B>) -> io::Result<()> {
+        while self.running {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                // Ignoring key releases, which some terminals send as well as presses
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Updates the interface based on the given key press
+    ///
+    /// Parameters
+    /// key:   The key that was pressed
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        self.message = None;
+
+        match &mut self.mode {
+            Mode::Normal => self.handle_normal_key(key.code),
+            Mode::Adding(description) => match key.code {
+                KeyCode::Char(character) => description.push(character),
+                KeyCode::Backspace => {
+                    description.pop();
+                }
+                KeyCode::Enter => {
+                    let description = description.clone();
+                    self.add_task(description);
+                }
+                KeyCode::Esc => self.mode = Mode::Normal,
+                _ => (),
+            },
+            Mode::Moving(list_index) => match key.code {
+                KeyCode::Up => *list_index = list_index.saturating_sub(1),
+                KeyCode::Down => {
+                    *list_index = (*list_index + 1).min(self.config.lists_len() - 1);
+                }
+                KeyCode::Enter => {
+                    let list_index = *list_index;
+                    self.move_task(list_index);
+                }
+                KeyCode::Esc => self.mode = Mode::Normal,
+                _ => (),
+            },
+            Mode::Deleting(_) => {
+                self.mode = Mode::Normal;
+
+                // As when confirming in the cli, anything other than yes leaves the task alone
+                match key.code {
+                    KeyCode::Char('y') => self.delete_task(),
+                    _ => self.message = Some(String::from("Nothing was deleted!")),
+                }
+            }
+        }
+    }
+
+    /// Handles a key press when the keys act on the selected task
+    ///
+    /// Parameters
+    /// key:   The key that was pressed
+    fn handle_normal_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => {
+                self.selected = (self.selected + 1).min(self.tasks.len().saturating_sub(1));
+            }
+            KeyCode::Left => self.switch_list(-1),
+            KeyCode::Right => self.switch_list(1),
+            KeyCode::Char('s') => self.start_task(),
+            KeyCode::Char('f') => self.finish_task(),
+            KeyCode::Char('r') => self.restart_task(),
+            KeyCode::Char('d') if self.assume_yes => self.delete_task(),
+            KeyCode::Char('d') => self.confirm_delete(),
+            KeyCode::Char('a') => self.mode = Mode::Adding(String::new()),
+            KeyCode::Char('m') if !self.tasks.is_empty() => {
+                self.mode = Mode::Moving(self.current_list_index());
+            }
+            KeyCode::Char('q') | KeyCode::Esc => self.running = false,
+            _ => (),
+        }
+    }
+
+    /// Returns the index of the current list in the config's lists
+    fn current_list_index(&self) -> usize {
+        let current_list = self.config.current_list();
+
+        self.config
+            .lists_iter()
+            .position(|list| list == &current_list)
+            .unwrap_or_default()
+    }
+
+    /// Switches the current list to the list the given offset away, wrapping around at either end
+    ///
+    /// Parameters
+    /// offset:   How many lists to move by
+    fn switch_list(&mut self, offset: isize) {
+        let lists: Vec<String> = self.config.lists_iter().cloned().collect();
+
+        let index = (self.current_list_index() as isize + offset).rem_euclid(lists.len() as isize);
+
+        // The list comes from the config so it is always valid
+        self.config
+            .set_current_list(lists[index as usize].clone())
+            .unwrap_or(());
+
+        // Refiltering the tasks so that the tasks vec holds the new current list
+        let mut all_tasks = std::mem::take(self.tasks);
+        all_tasks.append(self.other_tasks);
+
+        let (tasks, other_tasks) = crate::filter_task_vec(all_tasks, self.config);
+
+        *self.tasks = tasks;
+        *self.other_tasks = other_tasks;
+
+        self.selected = 0;
+    }
+
+    /// Returns the index in the tasks vec of the selected task, as tasks are shown with subtasks
+    /// under their parent rather than in the order of the vec
+    fn selected_index(&self) -> usize {
+        task_management::task_tree(self.tasks)
+            .get(self.selected)
+            .map(|(_, index)| *index)
+            .unwrap_or(self.selected)
+    }
+
+    /// Returns the selector for the selected task, for passing it to the commands the cli uses
+    fn selected_task(&self) -> Vec<TaskSelector> {
+        let task_id = task_management::task_tree(self.tasks)
+            .get(self.selected)
+            .map(|(task_id, _)| task_id.clone())
+            .unwrap_or(TaskId::from(self.selected + 1));
+
+        vec![TaskSelector::Id(task_id)]
+    }
+
+    /// Starts the selected task and starts timing it, as the start command does
+    fn start_task(&mut self) {
+        let arguments = StartCommand {
+            task_ids: self.selected_task(),
+            force: false,
+        };
+
+        let message = crate::start_task(self.tasks, self.sessions, arguments);
+
+        self.message = Some(String::from(message));
+    }
+
+    /// Finishes the selected task, as the finish command does
+    fn finish_task(&mut self) {
+        let arguments = FinishCommand {
+            task_ids: self.selected_task(),
+            force: false,
+        };
+
+        let message = crate::finish_task(self.tasks, arguments);
+
+        crate::auto_complete_parents(self.tasks, self.config);
+
+        self.message = Some(String::from(message));
+    }
+
+    /// Restarts the selected task, as the restart command does
+    fn restart_task(&mut self) {
+        let arguments = RestartCommand {
+            task_ids: self.selected_task(),
+        };
+
+        let message = crate::restart_task(self.tasks, arguments);
+
+        self.message = Some(String::from(message));
+    }
+
+    /// Asks the user to confirm deleting the selected task and its subtasks
+    fn confirm_delete(&mut self) {
+        if self.tasks.is_empty() {
+            return;
+        }
+
+        let indexes = task_management::with_subtasks(self.tasks, &[self.selected_index()]);
+
+        let details = confirm::describe_tasks(self.tasks, &indexes);
+
+        self.mode = Mode::Deleting(format!("Delete {}? y/N", details.join(", ")));
+    }
+
+    /// Deletes the selected task and its subtasks, as the delete command does once confirmed
+    fn delete_task(&mut self) {
+        let arguments = DeleteCommand {
+            task_ids: self.selected_task(),
+        };
+
+        let message = crate::delete_task(self.tasks, arguments, true);
+
+        self.selected = self.selected.min(self.tasks.len().saturating_sub(1));
+        self.message = Some(String::from(message));
+    }
+
+    /// Adds a task with the given description to the current list, as the add command does
+    ///
+    /// Parameters
+    /// description:   The description of the new task
+    fn add_task(&mut self, description: String) {
+        let arguments = AddCommand {
+            description: Some(description),
+            parent: None,
+            repeat: None,
+        };
+
+        match crate::add_task(self.tasks, arguments, self.config) {
+            Ok(message) => {
+                // Giving the task an id straight away so that it can be timed once started
+                crate::assign_task_uids(self.tasks);
+
+                self.selected = self.tasks.len() - 1;
+                self.mode = Mode::Normal;
+                self.message = Some(String::from(message));
+            }
+            // Staying in adding mode so that the description can be fixed
+            Err(err) => self.message = Some(String::from(err)),
+        }
+    }
+
+    /// Moves the selected task to the list at the given index
+    ///
+    /// Parameters
+    /// list_index:   The index of the list in the config's lists
+    fn move_task(&mut self, list_index: usize) {
+        self.mode = Mode::Normal;
+
+        let list = match self.config.lists_iter().nth(list_index) {
+            Some(list) => list.clone(),
+            None => return,
+        };
+
+        if list == self.config.current_list() || self.selected >= self.tasks.len() {
+            return;
+        }
+
+        // Subtasks move along with their parent, so they aren't left without one
+        let indexes = task_management::with_subtasks(self.tasks, &[self.selected_index()]);
+
+        let mut moved: Vec<Task> = indexes
+            .into_iter()
+            .rev()
+            .map(|index| self.tasks.remove(index))
+            .collect();
+
+        moved.reverse();
+
+        for mut task in moved {
+            task.set_list(list.clone());
+            self.other_tasks.push(task);
+        }
+
+        self.selected = self.selected.min(self.tasks.len().saturating_sub(1));
+        self.message = Some(String::from("Task moved!"));
+    }
+
+    /// Draws the lists on the left, the tasks in the current list on the right, and the help,
+    /// message or new task's description along the bottom
+    ///
+    /// Parameters
+    /// frame:   The frame to draw to
+    pub fn draw(&self, frame: &mut Frame) {
+        let [main_area, footer_area] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+
+        let [lists_area, tasks_area] =
+            Layout::horizontal([Constraint::Percentage(25), Constraint::Percentage(75)])
+                .areas(main_area);
+
+        let current_list = self.config.current_list();
+
+        let lists: Vec<ListItem> = self
+            .config
+            .lists_iter()
+            .map(|list| {
+                let count = self
+                    .tasks
+                    .iter()
+                    .chain(self.other_tasks.iter())
+                    .filter(|task| &task.list() == list)
+                    .count();
+
+                let mut line = Line::from(format!("{} ({})", list, count));
+
+                if list == &current_list {
+                    line = line.bold();
+                }
+
+                ListItem::new(line)
+            })
+            .collect();
+
+        let mut lists_state = ListState::default();
+
+        let lists_block = match self.mode {
+            Mode::Moving(list_index) => {
+                lists_state.select(Some(list_index));
+                Block::bordered().title(" Move to ")
+            }
+            _ => Block::bordered().title(" Lists "),
+        };
+
+        frame.render_stateful_widget(
+            List::new(lists)
+                .block(lists_block)
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            lists_area,
+            &mut lists_state,
+        );
+
+        let tasks: Vec<ListItem> = task_management::task_tree(self.tasks)
+            .into_iter()
+            .map(|(task_id, index)| {
+                let task = &self.tasks[index];
+
+                // Subtasks are indented under their parent, as in the tasks command
+                let indent = "    ".repeat(task_id.depth());
+
+                let status = match task.status() {
+                    TaskStatus::NotStarted => Span::styled("[x]", Color::LightRed),
+                    TaskStatus::InProgress => Span::styled("[~]", Color::LightYellow),
+                    TaskStatus::Completed => Span::styled("[✔]", Color::LightGreen),
+                    TaskStatus::Cancelled => Span::styled("[-]", Color::DarkGray),
+                    TaskStatus::Blocked => Span::styled("[!]", Color::LightMagenta),
+                    TaskStatus::Custom(_) => Span::raw("[?]"),
+                };
+
+                // Statuses from the workflow use the glyph and color from the config
+                let status = match self.config.status_definition(&task.status()) {
+                    Some(definition) => Span::styled(
+                        format!("[{}]", definition.glyph),
+                        definition.color.parse::<Color>().unwrap_or(Color::White),
+                    ),
+                    None => status,
+                };
+
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{}{}. ", indent, task_id)),
+                    status.bold(),
+                    Span::raw(format!(" {}", task.description())),
+                    match task.notes() {
+                        Some(_) => Span::styled(" ✎", Color::LightBlue),
+                        None => Span::raw(""),
+                    },
+                    match task_management::is_blocked(self.tasks, index) {
+                        true => Span::styled(" (blocked)", Color::LightRed),
+                        false => Span::raw(""),
+                    },
+                ]))
+            })
+            .collect();
+
+        let mut tasks_state = ListState::default();
+
+        if !self.tasks.is_empty() {
+            tasks_state.select(Some(self.selected));
+        }
+
+        frame.render_stateful_widget(
+            List::new(tasks)
+                .block(Block::bordered().title(format!(" {} ", current_list)))
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            tasks_area,
+            &mut tasks_state,
+        );
+
+        let footer = match (&self.mode, &self.message) {
+            (Mode::Adding(description), Some(message)) => {
+                format!("{} New task: {}_", message, description)
+            }
+            (Mode::Adding(description), None) => format!("New task: {}_", description),
+            (Mode::Moving(_), _) => {
+                String::from("↑/↓ choose a list  enter move the task  esc cancel")
+            }
+            (Mode::Deleting(question), _) => question.clone(),
+            (Mode::Normal, Some(message)) => message.clone(),
+            (Mode::Normal, None) => String::from(HELP),
+        };
+
+        frame.render_widget(Paragraph::new(footer), footer_area);
+    }
+}
+
+/// Takes over the terminal to run the full screen interface, restoring the terminal once the user
+/// quits
+///
+/// Parameters
+/// tasks:         The task vec of the current list
+/// other_tasks:   The vec containing tasks not currently in the active list
+/// sessions:      The recorded time tracking sessions
+/// config:        The user's config
+/// assume_yes:    Whether to delete tasks without asking the user to confirm
+pub fn run(
+    tasks: &mut Vec<Task>,
+    other_tasks: &mut Vec<Task>,
+    sessions: &mut Vec<TimeSession>,
+    config: &mut Config,
+    assume_yes: bool,
+) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+
+    let result = App::new(tasks, other_tasks, sessions, config, assume_yes).run(&mut terminal);
+
+    ratatui::restore();
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_helpers::task;
+
+    use crate::time_tracking;
+
+    use ratatui::backend::TestBackend;
+
+    /// Presses each of the given keys in the app
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        for key in keys {
+            app.handle_key(KeyEvent::from(*key));
+        }
+    }
+
+    /// Draws the app to a virtual terminal and returns what is on screen
+    fn render(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 10)).unwrap();
+
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect()
+    }
+
+    #[test]
+    /// Tests if the interface draws the lists and the tasks in the current list
+    fn draw_shows_lists_and_tasks() {
+        let mut config = Config::new();
+        config.add_list(String::from("Dev")).unwrap();
+
+        let mut tasks = vec![task("A basic task", TaskStatus::NotStarted, "Main")];
+        let mut other_tasks = vec![task("A dev task", TaskStatus::Completed, "Dev")];
+        let mut sessions = Vec::new();
+
+        let app = App::new(
+            &mut tasks,
+            &mut other_tasks,
+            &mut sessions,
+            &mut config,
+            false,
+        );
+
+        let screen = render(&app);
+
+        assert!(screen.contains("Main (1)"));
+        assert!(screen.contains("Dev (1)"));
+        assert!(screen.contains("1. [x] A basic task"));
+        assert!(!screen.contains("A dev task"));
+        assert!(screen.contains(HELP));
+    }
+
+    #[test]
+    /// Tests if the arrow keys and status keys update the selected task
+    fn keys_update_selected_task() {
+        let mut config = Config::new();
+
+        let mut tasks = vec![
+            task("A basic task", TaskStatus::NotStarted, "Main"),
+            task("Another basic task", TaskStatus::NotStarted, "Main"),
+            task("Yet another basic task", TaskStatus::NotStarted, "Main"),
+        ];
+
+        // Tasks are given ids when they are read, so that they can be timed
+        crate::assign_task_uids(&mut tasks);
+
+        let mut other_tasks = Vec::new();
+        let mut sessions = Vec::new();
+
+        let mut app = App::new(
+            &mut tasks,
+            &mut other_tasks,
+            &mut sessions,
+            &mut config,
+            false,
+        );
+
+        press(
+            &mut app,
+            &[
+                KeyCode::Char('s'),
+                KeyCode::Down,
+                KeyCode::Char('f'),
+                KeyCode::Down,
+                KeyCode::Char('d'),
+            ],
+        );
+
+        // Deleting asks first, as the cli does
+        assert!(render(&app).contains("Delete 3. Yet another basic task? y/N"));
+
+        press(&mut app, &[KeyCode::Char('y')]);
+
+        // Starting the first task again shows the same error as the cli
+        press(&mut app, &[KeyCode::Up, KeyCode::Up, KeyCode::Char('s')]);
+
+        assert!(render(&app).contains("Task is already in progress!"));
+
+        press(&mut app, &[KeyCode::Char('q')]);
+
+        assert!(!app.running);
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].status(), TaskStatus::InProgress);
+        assert_eq!(tasks[1].status(), TaskStatus::Completed);
+    }
+
+    #[test]
+    /// Tests if finishing the last subtask in the interface completes its parent, as the finish
+    /// command does when auto_complete_parents is on
+    fn finish_key_completes_parents() {
+        let mut config = Config::new();
+        config.set_auto_complete_parents(true);
+
+        let mut tasks = vec![
+            task("A parent task", TaskStatus::NotStarted, "Main"),
+            task("A subtask", TaskStatus::NotStarted, "Main"),
+        ];
+
+        crate::assign_task_uids(&mut tasks);
+        let parent = tasks[0].uid();
+        tasks[1].set_parent(Some(parent));
+
+        let mut other_tasks = Vec::new();
+        let mut sessions = Vec::new();
+
+        let mut app = App::new(
+            &mut tasks,
+            &mut other_tasks,
+            &mut sessions,
+            &mut config,
+            false,
+        );
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Char('f')]);
+
+        assert!(render(&app).contains("Task has been completed!"));
+
+        assert_eq!(tasks[0].status(), TaskStatus::Completed);
+    }
+
+    #[test]
+    /// Tests if tasks can be added by typing their description, and are timed once started
+    fn add_key_adds_tasks() {
+        let mut config = Config::new();
+
+        let mut tasks = Vec::new();
+        let mut other_tasks = Vec::new();
+        let mut sessions = Vec::new();
+
+        let mut app = App::new(
+            &mut tasks,
+            &mut other_tasks,
+            &mut sessions,
+            &mut config,
+            false,
+        );
+
+        press(&mut app, &[KeyCode::Char('a')]);
+
+        for character in "New".chars() {
+            press(&mut app, &[KeyCode::Char(character)]);
+        }
+
+        assert!(render(&app).contains("New task: New_"));
+
+        press(&mut app, &[KeyCode::Enter]);
+
+        assert_eq!(app.mode, Mode::Normal);
+
+        // Starting the new task times it, as starting it from the cli would
+        press(&mut app, &[KeyCode::Char('s')]);
+
+        assert_eq!(tasks[0].description(), "New");
+        assert!(time_tracking::is_running(&sessions, &tasks[0].uid()));
+    }
+
+    #[test]
+    /// Tests if tasks can be moved between lists, and the current list switched
+    fn move_and_switch_keys_work() {
+        let mut config = Config::new();
+        config.add_list(String::from("Dev")).unwrap();
+
+        let mut tasks = vec![task("A basic task", TaskStatus::NotStarted, "Main")];
+        let mut other_tasks = Vec::new();
+        let mut sessions = Vec::new();
+
+        let mut app = App::new(
+            &mut tasks,
+            &mut other_tasks,
+            &mut sessions,
+            &mut config,
+            false,
+        );
+
+        press(
+            &mut app,
+            &[KeyCode::Char('m'), KeyCode::Down, KeyCode::Enter],
+        );
+
+        assert!(app.tasks.is_empty());
+
+        press(&mut app, &[KeyCode::Right]);
+
+        assert_eq!(app.config.current_list(), "Dev");
+        assert!(render(&app).contains("1. [x] A basic task"));
+
+        assert_eq!(tasks[0].list(), "Dev");
+        assert!(other_tasks.is_empty());
+    }
+}
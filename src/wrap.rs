@@ -0,0 +1,93 @@
+use crate::config::Config;
+
+use ratatui::crossterm::terminal;
+
+/// The width used when the output isn't going to a terminal, such as when it is piped
+const DEFAULT_WIDTH: usize = 80;
+
+/// Returns the width of the user's terminal, or 80 columns if it can't be found
+pub fn terminal_width() -> usize {
+    match terminal::size() {
+        Ok((columns, _)) if columns > 0 => columns as usize,
+        _ => DEFAULT_WIDTH,
+    }
+}
+
+/// Returns the width output should be wrapped to, which is the config's wrap_width if it is set and
+/// otherwise the width of the terminal
+///
+/// Parameters
+/// config:   The user's config
+pub fn line_width(config: &Config) -> usize {
+    match config.wrap_width() {
+        0 => terminal_width(),
+        width => width,
+    }
+}
+
+/// Wraps the text into lines no wider than the given width, breaking between words where possible
+/// and splitting any word too long to fit on a line of its own
+///
+/// Parameters
+/// text:    The text to wrap
+/// width:   The most characters allowed on a line
+pub fn wrap(text: &str, width: usize) -> Vec<String> {
+    // A line must hold at least one character for the text to fit at all
+    let width = width.max(1);
+
+    let mut lines: Vec<String> = Vec::new();
+
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+
+        let line_length = line.chars().count();
+
+        if line_length > 0 && line_length + 1 + word.len() <= width {
+            line.push(' ');
+            line.extend(word);
+            continue;
+        }
+
+        if line_length > 0 {
+            lines.push(std::mem::take(&mut line));
+        }
+
+        while word.len() > width {
+            lines.push(word.drain(..width).collect());
+        }
+
+        line.extend(word);
+    }
+
+    if !line.is_empty() || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    /// Tests if wrap breaks lines between words
+    fn wrap_breaks_between_words() {
+        assert_eq!(
+            wrap("Look into wrapping the print out of tasks", 16),
+            vec!["Look into", "wrapping the", "print out of", "tasks"]
+        );
+        assert_eq!(wrap("A short task", 80), vec!["A short task"]);
+    }
+
+    #[test]
+    /// Tests if wrap splits words that are too long to fit on a line
+    fn wrap_splits_long_words() {
+        assert_eq!(
+            wrap("See https://example.com/a/long/path", 10),
+            vec!["See", "https://ex", "ample.com/", "a/long/pat", "h"]
+        );
+    }
+}